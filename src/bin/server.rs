@@ -1,8 +1,13 @@
+use std::collections::{HashMap, HashSet};
 use std::env;
-use dictionary_rs::{controller::{kafka::create_consumer, opensearch::{create, delete, IndexDocument}}, models::{browser::{browser_from_id, browsers, BrowserDocument}, form::{form_from_id, forms, FormDocument}, menu::{menu_from_id, menus, MenuDocument}, process::{process_from_id, processes, ProcessDocument}, window::{window_from_id, windows, WindowDocument}}};
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
+use dictionary_rs::{error::DictionaryError, controller::{cache::evict_response, dead_letter::{list_dead_letters, publish_dead_letter, replay_dead_letter, DeadLetterReason}, event::{event_metadata, is_processed_event, mark_processed_event, EventMetadata}, headers::{auth_failure_action, is_equal_secret, AuthFailureAction, MessageHeaders, RoutedDocument}, health::{liveness, readiness}, kafka::{create_consumer, kafka_host, next_backoff, subscription_status, SubscriptionStatus}, reindex::{execute_reindex, reindex, reindex_status, start_reindex}, topic::{register_resource_topics, registered_topics, topic_document, topic_index_prefix, unknown_topic_messages}, opensearch::{bulk, get_opensearch_client, set_index_templates_registered, BulkAction, BulkOperation, IndexDocument}}, models::{browser::Browser, form::Form, menu::Menu, process::Process, resource::{index_template_names, register_index_templates, resource_from_id, resources, DictionaryResource, ResolvedIndex, SearchParameters}, window::Window}};
 use dotenv::dotenv;
-use rdkafka::{Message, consumer::{CommitMode, Consumer}};
-use salvo::{conn::tcp::TcpAcceptor, cors::Cors, http::header, hyper::Method, prelude::*};
+use rdkafka::{Message, Offset, TopicPartitionList, consumer::{CommitMode, Consumer, ConsumerContext}, message::OwnedMessage};
+use salvo::{async_trait, conn::tcp::TcpAcceptor, cors::Cors, http::{header, HeaderValue}, hyper::Method, prelude::*};
 extern crate serde_json;
 use serde::Serialize;
 use simple_logger::SimpleLogger;
@@ -13,6 +18,23 @@ async fn main() {
     dotenv().ok();
     SimpleLogger::new().env().init().unwrap();
 
+	//  Shared OpenSearch client for handlers and consumer
+	match get_opensearch_client() {
+		Ok(_) => log::info!("OpenSearch client initialized"),
+		Err(error) => log::error!("OpenSearch client error: {}", error),
+	};
+	if !register_templates().await {
+		tokio::spawn(retry_register_templates());
+	}
+	register_topics();
+
+	//  `server reindex <index>...` reindex the indexes and exit
+	let arguments: Vec<String> = env::args().collect();
+	if arguments.get(1).map(|command| command.as_str()) == Some("reindex") {
+		let is_success = reindex_command(&arguments[2..]).await;
+		std::process::exit(if is_success { 0 } else { 1 });
+	}
+
 	let port: String = match env::var("PORT") {
         Ok(value) => value,
         Err(_) => {
@@ -37,23 +59,62 @@ async fn main() {
     //  Send Device Info
     let cors_handler = Cors::new()
         .allow_origin(&allowed_origin.to_owned())
-        .allow_methods(vec![Method::OPTIONS, Method::GET])
+        .allow_methods(vec![Method::OPTIONS, Method::GET, Method::POST])
         .allow_headers(vec![header::ACCESS_CONTROL_REQUEST_METHOD, header::ACCESS_CONTROL_REQUEST_HEADERS, header::AUTHORIZATION])
+        .expose_headers(vec!["x-dictionary-index", "x-dictionary-index-level"])
         .into_handler()
     ;
 
 	let router = Router::new()
         .hoop(cors_handler)
+        .push(
+            // /health
+            Router::with_path("health")
+				.push(
+					// /health/live
+					Router::with_path("live")
+						.get(get_liveness)
+				)
+				.push(
+					// /health/ready
+					Router::with_path("ready")
+						.get(get_readiness)
+				)
+        )
         .push(
             // /api
             Router::with_path("api")
 				.options(options_response)
 				.get(get_system_info)
 				.push(
+					// /api/admin
+					Router::with_path("admin")
+						.hoop(admin_authorization)
+						.push(
+							// /api/admin/indexes/:index/reindex
+							Router::with_path("indexes/<index>/reindex")
+								.options(options_response)
+								.get(get_reindex_status)
+								.post(post_reindex)
+						)
+						.push(
+							// /api/admin/dead-letters
+							Router::with_path("dead-letters")
+								.options(options_response)
+								.get(get_dead_letters)
+								.push(
+									// /api/admin/dead-letters/:partition/:offset/replay
+									Router::with_path("<partition>/<offset>/replay")
+										.options(options_response)
+										.post(post_replay_dead_letter)
+								)
+						)
+				)
+				.push(
                     // /api/security/menus
                     Router::with_path("security/menus")
 						.options(options_response)
-                        .get(get_menu)
+                        .get(ResourceHandler::<Menu>::new())
                 )
 				.push(
 					// /api/dictionary
@@ -62,48 +123,48 @@ async fn main() {
 							// /api/dictionary/browsers/
 							Router::with_path("browsers")
 								.options(options_response)
-								.get(get_browsers)
+								.get(ResourceHandler::<Browser>::new())
 								.push(
 									// /api/dictionary/browsers/:id
 									Router::with_path("<id>")
 										.options(options_response)
-										.get(get_browsers)
+										.get(ResourceHandler::<Browser>::new())
 								)
 						)
 						.push(
 							// /api/dictionary/forms/
 							Router::with_path("forms")
 								.options(options_response)
-								.get(get_forms)
+								.get(ResourceHandler::<Form>::new())
 								.push(
 									// /api/dictionary/forms/:id
 									Router::with_path("<id>")
 										.options(options_response)
-										.get(get_forms)
+										.get(ResourceHandler::<Form>::new())
 								)
 						)
 						.push(
 						// /api/dictionary/processes
 					Router::with_path("processes")
 								.options(options_response)
-								.get(get_processes)
+								.get(ResourceHandler::<Process>::new())
 								.push(
 									// /api/dictionary/processes/:id
 									Router::with_path("<id>")
 										.options(options_response)
-										.get(get_processes)
+										.get(ResourceHandler::<Process>::new())
 								)
                         )
                         .push(
                             // /api/dictionary/windows/
                             Router::with_path("windows")
 								.options(options_response)
-                                .get(get_windows)
+                                .get(ResourceHandler::<Window>::new())
 								.push(
 									// /api/dictionary/windows/:id
 									Router::with_path("<id>")
 										.options(options_response)
-										.get(get_windows)
+										.get(ResourceHandler::<Window>::new())
 								)
 						)
 				)
@@ -111,23 +172,299 @@ async fn main() {
     ;
     log::info!("{:#?}", router);
 
-    let mut futures = vec![tokio::spawn(async move { Server::new(acceptor).serve(router).await; })];
+    let server = Server::new(acceptor);
+    let server_handle = server.handle();
+    let mut futures = vec![tokio::spawn(async move { server.serve(router).await; })];
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
 
 	// Kafka Queue
-	let kafka_enabled: String = match env::var("KAFKA_ENABLED") {
-		Ok(value) => value,
-		Err(_) => {
-			log::info!("Variable `KAFKA_ENABLED` Not found from enviroment, as default Y");
-			"Y".to_owned()
-		}.to_owned()
-	};
-	if kafka_enabled.trim().eq("Y") {
+	if is_kafka_enabled() {
         log::info!("Kafka Consumer is enabled");
-        futures.push(tokio::spawn(async move { consume_queue().await; }));
+        futures.push(tokio::spawn(async move { consume_queue(shutdown_receiver).await; }));
     } else {
         log::info!("Kafka Consumer is disabled");
     }
+
+	//  Stop accepting requests and messages, the running requests and batch are finished before the deadline
+	let shutdown_timeout = shutdown_timeout();
+	tokio::spawn(async move {
+		shutdown_signal().await;
+		log::info!("Shutdown started, waiting up to {:?}", shutdown_timeout);
+		server_handle.stop_graceful(shutdown_timeout);
+		shutdown_sender.send_replace(true);
+		tokio::time::sleep(shutdown_timeout).await;
+		log::error!("Shutdown deadline of {:?} exceeded, exiting", shutdown_timeout);
+		std::process::exit(1);
+	});
     join_all(futures).await;
+	log::info!("Shutdown completed");
+}
+
+/// Deadline to finish the running requests and batch from `SHUTDOWN_TIMEOUT_SECONDS`
+fn shutdown_timeout() -> Duration {
+	let seconds: u64 = match env::var("SHUTDOWN_TIMEOUT_SECONDS") {
+		Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+			log::error!("Variable `SHUTDOWN_TIMEOUT_SECONDS` is invalid, as default 30");
+			30
+		}),
+		Err(_) => {
+			log::info!("Variable `SHUTDOWN_TIMEOUT_SECONDS` Not found from enviroment, as default 30");
+			30
+		}
+	};
+	Duration::from_secs(seconds)
+}
+
+/// Wait for `SIGINT` or `SIGTERM`
+async fn shutdown_signal() {
+	let interrupt = async {
+		if let Err(error) = tokio::signal::ctrl_c().await {
+			log::error!("Signal error: {}", error);
+			std::future::pending::<()>().await;
+		}
+	};
+	#[cfg(unix)]
+	let terminate = async {
+		match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+			Ok(mut signal) => {
+				signal.recv().await;
+			},
+			Err(error) => {
+				log::error!("Signal error: {}", error);
+				std::future::pending::<()>().await;
+			},
+		}
+	};
+	#[cfg(not(unix))]
+	let terminate = std::future::pending::<()>();
+	tokio::select! {
+		_ = interrupt => {},
+		_ = terminate => {},
+	}
+}
+
+/// Index templates of all resources, without them new indexes are created with the mapping of the document.
+/// Returns `false` when some template is not registered
+async fn register_templates() -> bool {
+	let results = vec![
+		register_index_templates::<Menu>().await,
+		register_index_templates::<Process>().await,
+		register_index_templates::<Browser>().await,
+		register_index_templates::<Window>().await,
+		register_index_templates::<Form>().await,
+	];
+	let errors: Vec<DictionaryError> = results.into_iter().filter_map(|result| result.err()).collect();
+	for error in &errors {
+		log::error!("Index template error: {}", error);
+	}
+	set_index_templates_registered(errors.is_empty());
+	errors.is_empty()
+}
+
+/// Register the index templates again until OpenSearch accepts them, the readiness is down meanwhile
+async fn retry_register_templates() {
+	let mut backoff = Duration::from_secs(1);
+	let maximum_backoff = Duration::from_secs(60);
+	loop {
+		log::warn!("Index templates are not registered, retrying in {:?}", backoff);
+		tokio::time::sleep(backoff).await;
+		if register_templates().await {
+			log::info!("Index templates registered");
+			return;
+		}
+		backoff = backoff.saturating_mul(2).min(maximum_backoff);
+	}
+}
+
+/// Topics consumed by each resource, other topics can be registered with `register_topic_handler`
+fn register_topics() {
+	register_resource_topics::<Menu>();
+	register_resource_topics::<Process>();
+	register_resource_topics::<Browser>();
+	register_resource_topics::<Window>();
+	register_resource_topics::<Form>();
+}
+
+/// Kafka consumer is enabled with `KAFKA_ENABLED` as `Y`
+fn is_kafka_enabled() -> bool {
+	static KAFKA_ENABLED: OnceLock<bool> = OnceLock::new();
+	*KAFKA_ENABLED.get_or_init(|| {
+		let kafka_enabled: String = match env::var("KAFKA_ENABLED") {
+			Ok(value) => value,
+			Err(_) => {
+				log::info!("Variable `KAFKA_ENABLED` Not found from enviroment, as default Y");
+				"Y".to_owned()
+			}
+		};
+		kafka_enabled.trim().eq("Y")
+	})
+}
+
+/// Index templates registered at startup by all resources
+fn template_names() -> Vec<String> {
+	let mut names = index_template_names::<Menu>();
+	names.extend(index_template_names::<Process>());
+	names.extend(index_template_names::<Browser>());
+	names.extend(index_template_names::<Window>());
+	names.extend(index_template_names::<Form>());
+	names
+}
+
+/// Topics to subscribe from `KAFKA_QUEUES`, the registered topics as default
+fn kafka_queues() -> String {
+	match env::var("KAFKA_QUEUES") {
+		Ok(value) => value,
+		Err(_) => {
+			log::info!("Variable `KAFKA_QUEUES` Not found from enviroment, loaded with registered topics");
+			registered_topics().join(" ")
+		}
+	}
+}
+
+/// Document of the resource that owns the index, e.g. `window_es_mx_11` is a window index
+fn index_document(_index_name: &str) -> Option<Box<dyn IndexDocument + Send>> {
+	let _index_name = _index_name.trim().to_lowercase();
+	let prefix = _index_name.split(['_', '-']).next().unwrap_or("").to_owned();
+	match prefix.as_str() {
+		"menu" => Some(Box::new(Menu::with_index(None, _index_name))),
+		"process" => Some(Box::new(Process::with_index(None, _index_name))),
+		"browser" => Some(Box::new(Browser::with_index(None, _index_name))),
+		"window" => Some(Box::new(Window::with_index(None, _index_name))),
+		"form" => Some(Box::new(Form::with_index(None, _index_name))),
+		_ => None,
+	}
+}
+
+async fn reindex_command(_index_names: &[String]) -> bool {
+	if _index_names.is_empty() {
+		log::error!("Usage: server reindex <index>...");
+		return false;
+	}
+	let mut is_success = true;
+	for index_name in _index_names {
+		let _document = match index_document(index_name) {
+			Some(document) => document,
+			None => {
+				log::error!("Index {:?} does not belong to a dictionary resource", index_name);
+				is_success = false;
+				continue;
+			}
+		};
+		match reindex(_document.as_ref()).await {
+			Ok(status) => log::info!("Reindex of {:?} completed into {:?}: {} documents", status.index_name, status.destination_index, status.total),
+			Err(error) => {
+				log::error!("Reindex of {:?} failed: {}", index_name, error);
+				is_success = false;
+			}
+		}
+	}
+	is_success
+}
+
+/// Start the reindex of an index in background, the progress is returned by `get_reindex_status`
+#[handler]
+async fn post_reindex<'a>(_req: &mut Request, _res: &mut Response) {
+	let _index_name: String = _req.param::<String>("index").unwrap_or_default();
+	let _document = match index_document(&_index_name) {
+		Some(document) => document,
+		None => return render_error(_res, DictionaryError::InvalidParameter(format!("Index {:?} does not belong to a dictionary resource", _index_name))),
+	};
+	match start_reindex(_document.as_ref()).await {
+		Ok(status) => {
+			let _status = status.clone();
+			tokio::spawn(async move {
+				if let Err(error) = execute_reindex(_document.as_ref(), _status).await {
+					log::error!("{}", error);
+				}
+			});
+			_res.status_code(StatusCode::ACCEPTED)
+				.render(
+					Json(status)
+				)
+			;
+		},
+		Err(error) => render_error(_res, error)
+	}
+}
+
+#[handler]
+async fn get_reindex_status<'a>(_req: &mut Request, _res: &mut Response) {
+	let _index_name: String = _req.param::<String>("index").unwrap_or_default().trim().to_lowercase();
+	match reindex_status(&_index_name) {
+		Some(status) => {
+			_res.render(
+				Json(status)
+			);
+		},
+		None => render_error(_res, DictionaryError::IndexNotFound(format!("Index {:?} without reindex", _index_name)))
+	}
+}
+
+/// Token of the admin endpoints from `ADMIN_TOKEN`, without it the admin endpoints are disabled
+fn admin_token() -> Option<&'static str> {
+	static ADMIN_TOKEN: OnceLock<Option<String>> = OnceLock::new();
+	ADMIN_TOKEN.get_or_init(|| {
+		match env::var("ADMIN_TOKEN") {
+			Ok(value) if !value.trim().is_empty() => Some(value.trim().to_owned()),
+			_ => {
+				log::info!("Variable `ADMIN_TOKEN` Not found from enviroment, admin endpoints are disabled");
+				None
+			}
+		}
+	}).as_deref()
+}
+
+/// Admin endpoints change indexes and publish messages, they need `Authorization: Bearer <ADMIN_TOKEN>`
+#[handler]
+async fn admin_authorization(_req: &mut Request, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+	if _req.method() == Method::OPTIONS {
+		return;
+	}
+	let _token: Option<String> = _req.header::<String>(header::AUTHORIZATION)
+		.map(|value| value.strip_prefix("Bearer ").unwrap_or(&value).trim().to_owned());
+	let error = match (admin_token(), _token) {
+		(Some(admin_token), Some(token)) if is_equal_secret(&token, admin_token) => return,
+		(None, _) => DictionaryError::Unauthorized("Admin endpoints are disabled without `ADMIN_TOKEN`".to_owned()),
+		(Some(_), None) => DictionaryError::Unauthorized("Admin token is mandatory".to_owned()),
+		(Some(_), Some(_)) => DictionaryError::Unauthorized("Invalid admin token".to_owned()),
+	};
+	render_error(_res, error);
+	_ctrl.skip_rest();
+}
+
+/// Messages of the dead-letter topic, optionally from `partition` and `offset`, at most `limit` (default 100)
+#[handler]
+async fn get_dead_letters<'a>(_req: &mut Request, _res: &mut Response) {
+	let _partition: Option<i32> = _req.query::<i32>("partition");
+	let _offset: Option<i64> = _req.query::<i64>("offset");
+	let _limit: usize = _req.query::<usize>("limit").unwrap_or(100).clamp(1, 1000);
+	match list_dead_letters(_partition, _offset, _limit).await {
+		Ok(dead_letters) => {
+			_res.render(
+				Json(dead_letters)
+			);
+		},
+		Err(error) => render_error(_res, error)
+	}
+}
+
+/// Publish again a dead-lettered message to the topic where it was consumed
+#[handler]
+async fn post_replay_dead_letter<'a>(_req: &mut Request, _res: &mut Response) {
+	let _partition: Option<i32> = _req.param::<i32>("partition");
+	let _offset: Option<i64> = _req.param::<i64>("offset");
+	let (_partition, _offset) = match (_partition, _offset) {
+		(Some(partition), Some(offset)) => (partition, offset),
+		_ => return render_error(_res, DictionaryError::InvalidParameter("Partition and Offset must be numbers".to_owned())),
+	};
+	match replay_dead_letter(_partition, _offset).await {
+		Ok(dead_letter) => {
+			_res.render(
+				Json(dead_letter)
+			);
+		},
+		Err(error) => render_error(_res, error)
+	}
 }
 
 #[handler]
@@ -135,11 +472,36 @@ async fn options_response<'a>(_req: &mut Request, _res: &mut Response) {
 	_res.status_code(StatusCode::NO_CONTENT);
 }
 
+/// `200` while the process is running
+#[handler]
+async fn get_liveness<'a>(_req: &mut Request, _res: &mut Response) {
+	_res.status_code(StatusCode::OK)
+		.render(
+			Json(liveness())
+		)
+	;
+}
+
+/// `200` when all components are up, otherwise `503` with the status of each component
+#[handler]
+async fn get_readiness<'a>(_req: &mut Request, _res: &mut Response) {
+	let readiness_response = readiness(is_kafka_enabled(), &template_names()).await;
+	let status_code = if readiness_response.is_up() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+	_res.status_code(status_code)
+		.render(
+			Json(readiness_response)
+		)
+	;
+}
+
 #[derive(Serialize)]
 struct SystemInfoResponse {
 	version: String,
 	is_kafka_enabled: bool,
 	kafka_queues: String,
+	/// Messages skipped by topic because the topic does not have handler
+	unknown_topics: HashMap<String, u64>,
+	kafka_subscription: SubscriptionStatus,
 }
 
 #[handler]
@@ -153,25 +515,14 @@ async fn get_system_info<'a>(_req: &mut Request, _res: &mut Response) {
 	};
 
 	// Kafka Queue
-	let kafka_enabled: String = match env::var("KAFKA_ENABLED") {
-		Ok(value) => value,
-		Err(_) => {
-			log::info!("Variable `KAFKA_ENABLED` Not found from enviroment, as default Y");
-			"Y".to_owned()
-		}.to_owned()
-	};
-	let kafka_queues: String = match env::var("KAFKA_QUEUES") {
-		Ok(value) => value.clone(),
-		Err(_) => {
-			log::info!("Variable `KAFKA_QUEUES` Not found from enviroment, loaded with `default` value");
-			"menu process browser window".to_owned()
-		}.to_owned()
-	};
+	let kafka_queues: String = kafka_queues();
 
 	let system_info_response = SystemInfoResponse {
 		version: version.to_string(),
-		is_kafka_enabled: kafka_enabled.trim().eq("Y"),
-		kafka_queues: kafka_queues
+		is_kafka_enabled: is_kafka_enabled(),
+		kafka_queues,
+		unknown_topics: unknown_topic_messages(),
+		kafka_subscription: subscription_status()
 	};
 
 	_res.status_code(StatusCode::OK)
@@ -185,230 +536,94 @@ async fn get_system_info<'a>(_req: &mut Request, _res: &mut Response) {
 #[derive(Serialize)]
 struct ErrorResponse {
 	status: u16,
+	code: String,
 	message: String
 }
 
-#[handler]
-async fn get_forms<'a>(_req: &mut Request, _res: &mut Response) {
-	let _id: Option<i32> = _req.param::<i32>("id");
-	let _language: Option<&String> = _req.queries().get("language");
-	let _client_id: Option<&String> = _req.queries().get("client_id");
-	let _role_id: Option<&String> = _req.queries().get("role_id");
-	let _user_id: Option<&String> = _req.queries().get("user_id");
-
-	if _id.is_some() {
-		match form_from_id(_id, _language, _client_id, _role_id, _user_id).await {
-			Ok(form) => _res.render(Json(form)),
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-		}
-	} else {
-		let _search_value: Option<&String> = _req.queries().get("search_value");
+fn error_status_code(error: &DictionaryError) -> StatusCode {
+	match error {
+		DictionaryError::MandatoryParameter(_) | DictionaryError::InvalidParameter(_) => StatusCode::BAD_REQUEST,
+		DictionaryError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+		DictionaryError::IndexNotFound(_) | DictionaryError::DocumentNotFound(_) => StatusCode::NOT_FOUND,
+		DictionaryError::OpenSearch(_) | DictionaryError::Kafka(_) => StatusCode::BAD_GATEWAY,
+		DictionaryError::DocumentRejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+		DictionaryError::Deserialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
 
-		match forms(_language, _client_id, _role_id, _user_id, _search_value).await {
-			Ok(forms_list) => {
-				_res.render(Json(forms_list));
-			},
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-		}
+fn render_error(_res: &mut Response, error: DictionaryError) {
+	let status_code = error_status_code(&error);
+	let error_response = ErrorResponse {
+		status: status_code.into(),
+		code: error.code().to_owned(),
+		message: error.to_string()
+	};
+	_res.render(
+		Json(error_response)
+	);
+	_res.status_code(status_code);
+}
+
+/// Report the index and the level resolved by the index policy
+fn render_index_headers(_res: &mut Response, _index: &ResolvedIndex) {
+	if let Ok(value) = HeaderValue::from_str(&_index.index_name) {
+		_res.headers_mut().insert("x-dictionary-index", value);
 	}
+	_res.headers_mut().insert("x-dictionary-index-level", HeaderValue::from_static(_index.level.name()));
 }
 
-#[handler]
-async fn get_menu<'a>(_req: &mut Request, _res: &mut Response) {
-    let _id = _req.param::<i32>("id");
-	let _language = _req.queries().get("language");
-	let _client_id = _req.queries().get("client_id");
-	let _role_id = _req.queries().get("role_id");
-	let _user_id = _req.queries().get("user_id");
-
-	if _id.is_some() {
-		match menu_from_id(_id, _language, _client_id, _role_id, _user_id).await {
-			Ok(menu) => _res.render(Json(menu)),
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-		}
-	} else {
-        let _search_value = _req.queries().get("search_value");
-		let _page_number: Option<&String> = _req.queries().get("page_number");
-		let _page_size: Option<&String> = _req.queries().get("page_size");
-		match menus(_language, _client_id, _role_id, _user_id, _search_value, _page_number, _page_size).await {
-            Ok(menus_list) => {
-                _res.render(Json(menus_list));
-            },
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-        }
-    }
+/// Generic handler for `/<resources>` and `/<resources>/<id>` of any dictionary resource
+struct ResourceHandler<T: DictionaryResource> {
+	resource: PhantomData<fn() -> T>
 }
 
-#[handler]
-async fn get_processes<'a>(_req: &mut Request, _res: &mut Response) {
-    let _id = _req.param::<i32>("id");
-    let _language = _req.queries().get("language");
-    let _client_id = _req.queries().get("client_id");
-    let _role_id = _req.queries().get("role_id");
-    let _user_id = _req.queries().get("user_id");
-    let _search_value = _req.queries().get("search_value");
-
-    if _id.is_some() {
-		match process_from_id(_id, _language, _client_id, _role_id, _user_id).await {
-            Ok(process) => _res.render(Json(process)),
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-        }
-    } else {
-        match processes(_language, _client_id, _role_id, _user_id, _search_value).await {
-            Ok(processes_list) => {
-                _res.render(Json(processes_list));
-            },
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-        }
-    }
+impl<T: DictionaryResource> ResourceHandler<T> {
+	fn new() -> Self {
+		ResourceHandler {
+			resource: PhantomData
+		}
+	}
 }
 
-#[handler]
-async fn get_browsers<'a>(_req: &mut Request, _res: &mut Response) {
-    let _id = _req.param::<i32>("id");
-    let _language = _req.queries().get("language");
-    let _client_id = _req.queries().get("client_id");
-    let _role_id = _req.queries().get("role_id");
-    let _user_id = _req.queries().get("user_id");
-    let _search_value = _req.queries().get("search_value");
-
-    if _id.is_some() {
-		match browser_from_id(_id, _language, _client_id, _role_id, _user_id).await {
-            Ok(browser) => _res.render(Json(browser)),
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-			}
-        }
-    } else {
-        match browsers(_language, _client_id, _role_id, _user_id, _search_value).await {
-            Ok(browsers_list) => {
-                _res.render(Json(browsers_list));
-            },
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+#[async_trait]
+impl<T: DictionaryResource> Handler for ResourceHandler<T> {
+	async fn handle(&self, _req: &mut Request, _depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+		let _id: Option<i32> = _req.param::<i32>("id");
+		let _language: Option<&String> = _req.queries().get("language");
+		let _client_id: Option<&String> = _req.queries().get("client_id");
+		let _role_id: Option<&String> = _req.queries().get("role_id");
+		let _user_id: Option<&String> = _req.queries().get("user_id");
+
+		if _id.is_some() {
+			match resource_from_id::<T>(_id, _language, _client_id, _role_id, _user_id).await {
+				Ok(response) => {
+					render_index_headers(_res, &response.index);
+					_res.render(Json(response.resource));
+				},
+				Err(error) => render_error(_res, error)
 			}
-        }
-    }
-}
+		} else {
+			let _parameters = SearchParameters {
+				search_value: _req.queries().get("search_value"),
+				page_number: _req.queries().get("page_number"),
+				page_size: _req.queries().get("page_size"),
+				page_token: _req.queries().get("page_token")
+			};
 
-#[handler]
-async fn get_windows<'a>(_req: &mut Request, _res: &mut Response) {
-    let _id = _req.param::<i32>("id");
-    let _language = _req.queries().get("language");
-    let _client_id = _req.queries().get("client_id");
-    let _role_id = _req.queries().get("role_id");
-    let _user_id = _req.queries().get("user_id");
-    let _search_value = _req.queries().get("search_value");
-
-    if _id.is_some() {
-		match window_from_id(_id, _language, _client_id, _role_id, _user_id).await {
-            Ok(window) => _res.render(Json(window)),
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+			match resources::<T>(_language, _client_id, _role_id, _user_id, _parameters).await {
+				Ok(resources_list) => {
+					render_index_headers(_res, &resources_list.index);
+					_res.render(Json(resources_list));
+				},
+				Err(error) => render_error(_res, error)
 			}
-        }
-    } else {
-        match windows(_language, _client_id, _role_id, _user_id, _search_value).await {
-            Ok(windows_list) => {
-                _res.render(Json(windows_list));
-            },
-			Err(error) => {
-				let error_response = ErrorResponse {
-					status: StatusCode::INTERNAL_SERVER_ERROR.into(),
-					message: error.to_string()
-				};
-				_res.render(
-					Json(error_response)
-				);
-				_res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
-            }
-        }
-    }
+		}
+	}
 }
 
-async fn consume_queue() {
-	let kafka_host = match env::var("KAFKA_HOST") {
-        Ok(value) => value,
-        Err(_) => {
-            log::info!("Variable `KAFKA_HOST` Not found from enviroment, loaded from local IP");
-            "127.0.0.1:9092".to_owned()
-        }.to_owned(),
-    };
+/// Consume until the `shutdown` is sent, the current batch is finished and its offsets are committed before close the consumer
+async fn consume_queue(mut shutdown: watch::Receiver<bool>) {
+	let kafka_host = kafka_host();
 	log::info!("Kafka queue: {:?}", kafka_host.to_owned());
 
     let kafka_group =  match env::var("KAFKA_GROUP") {
@@ -418,163 +633,668 @@ async fn consume_queue() {
             "default".to_owned()
         }.to_owned(),
     };
-	let kafka_queues: String = match env::var("KAFKA_QUEUES") {
-        Ok(value) => value.clone(),
-        Err(_) => {
-            log::info!("Variable `KAFKA_QUEUES` Not found from enviroment, loaded with `default` value");
-			"menu process browser window".to_owned()
-		}.to_owned()
-    };
+	let kafka_queues: String = kafka_queues();
 
     let topics: Vec<&str> = kafka_queues.split_whitespace().collect();
 	log::info!("Topics to Subscribed: {:?}", topics.to_owned());
 
-    let consumer_result = create_consumer(&kafka_host, &kafka_group, &topics);
+    let batch_size: usize = match env::var("KAFKA_BATCH_SIZE") {
+        Ok(value) => value.trim().parse::<usize>().ok().filter(|size| *size > 0).unwrap_or_else(|| {
+            log::error!("Variable `KAFKA_BATCH_SIZE` is invalid, as default 500");
+            500
+        }),
+        Err(_) => {
+            log::info!("Variable `KAFKA_BATCH_SIZE` Not found from enviroment, as default 500");
+            500
+        }
+    };
+    let batch_window: Duration = match env::var("KAFKA_BATCH_WINDOW_MS") {
+        Ok(value) => Duration::from_millis(value.trim().parse::<u64>().unwrap_or_else(|_| {
+            log::error!("Variable `KAFKA_BATCH_WINDOW_MS` is invalid, as default 1000");
+            1000
+        })),
+        Err(_) => {
+            log::info!("Variable `KAFKA_BATCH_WINDOW_MS` Not found from enviroment, as default 1000");
+            Duration::from_millis(1000)
+        }
+    };
+
+    let retry_policy = RetryPolicy::from_env();
+
+    let consumer_result = tokio::select! {
+        result = create_consumer(&kafka_host, &kafka_group, &topics) => result,
+        _ = shutdown.changed() => {
+            log::info!("Kafka subscription stopped by shutdown");
+            return;
+        },
+    };
     match consumer_result {
         Ok(consumer) => {
-            loop {
-                match consumer.recv().await {
-                    Err(e) => log::error!("Kafka error: {}", e),
-                    Ok(message) => {
-                        let payload = match message.payload_view::<str>() {
-                            None => "",
-                            Some(Ok(s)) => s,
-                            Some(Err(e)) => {
-                                log::info!("Error while deserializing message payload: {:?}", e);
-                                ""
-                            }
-                        };
-                        let key = match message.key_view::<str>() {
-                            None => "",
-                            Some(Ok(s)) => s,
-                            Some(Err(e)) => {
-                                log::info!("Error while deserializing message key: {:?}", e);
-                                ""
+            let mut partition_offsets = PartitionOffsets::default();
+            //  The backoff of the receive errors does not start at zero, even without retry backoff
+            let initial_receive_backoff = retry_policy.backoff.max(Duration::from_millis(100));
+            let mut receive_backoff = initial_receive_backoff;
+            while !*shutdown.borrow() {
+                //  Wait for the first message, then collect until the batch is full or the window is over
+                let mut messages: Vec<OwnedMessage> = Vec::with_capacity(batch_size);
+                tokio::select! {
+                    _ = shutdown.changed() => break,
+                    result = consumer.recv() => match result {
+                        Err(e) => {
+                            //  A persistent broker error is not read again without wait
+                            log::error!("Kafka error, retrying in {:?}: {}", receive_backoff, e);
+                            let mut backoff_shutdown = shutdown.clone();
+                            tokio::select! {
+                                _ = backoff_shutdown.wait_for(|is_shutdown| *is_shutdown) => {},
+                                _ = tokio::time::sleep(receive_backoff) => {},
                             }
-                        };
-                        let event_type = key.replace("\"", "");
-                        let topic = message.topic();
-                        if topic == "menu" {
-                            let _document = match serde_json::from_str(payload) {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    log::warn!("{}", error);
-                                    MenuDocument {
-                                        document: None
-                                    }
-                                },
-                            };
-                            if _document.document.is_some() {
-                                let _menu_document: &dyn IndexDocument = &(_document.document.unwrap());
-                                match process_index(event_type, _menu_document).await {
-                                    Ok(_) => consumer.commit_message(&message, CommitMode::Async).unwrap(),
-                                    Err(error) => log::warn!("{}", error)
-                                }
-                            }
-                        } else if topic == "process" {
-                            let _document = match serde_json::from_str(payload) {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    log::warn!("{}", error);
-                                    ProcessDocument {
-                                        document: None
-                                    }
-                                },
-                            };
-                            if _document.document.is_some() {
-                                let _process_document: &dyn IndexDocument = &(_document.document.unwrap());
-                                match process_index(event_type, _process_document).await {
-                                    Ok(_) => consumer.commit_message(&message, CommitMode::Async).unwrap(),
-                                    Err(error) => log::warn!("{}", error)
-                                }
-                            }
-                        } else if topic == "browser" {
-                            let _document = match serde_json::from_str(payload) {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    log::warn!("{}", error);
-                                    BrowserDocument {
-                                        document: None
-                                    }
-                                },
-                            };
-                            if _document.document.is_some() {
-                                let _browser_document: &dyn IndexDocument = &(_document.document.unwrap());
-                                match process_index(event_type, _browser_document).await {
-                                    Ok(_) => consumer.commit_message(&message, CommitMode::Async).unwrap(),
-                                    Err(error) => log::warn!("{}", error)
-                                }
-                            }
-                        } else if topic == "window" {
-                            let _document = match serde_json::from_str(payload) {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    log::warn!("{}", error);
-                                    WindowDocument {
-                                        document: None
-                                    }
-                                },
-                            };
-                            if _document.document.is_some() {
-                                let _window_document: &dyn IndexDocument = &(_document.document.unwrap());
-                                match process_index(event_type, _window_document).await {
-                                    Ok(_) => consumer.commit_message(&message, CommitMode::Async).unwrap(),
-                                    Err(error) => log::warn!("{}", error)
-                                }
+                            receive_backoff = next_backoff(receive_backoff, retry_policy.maximum_backoff);
+                            continue;
+                        },
+                        Ok(message) => messages.push(message.detach()),
+                    },
+                };
+                receive_backoff = initial_receive_backoff;
+                let batch_deadline = Instant::now() + batch_window;
+                while messages.len() < batch_size && !*shutdown.borrow() {
+                    tokio::select! {
+                        _ = shutdown.changed() => break,
+                        result = tokio::time::timeout_at(batch_deadline, consumer.recv()) => match result {
+                            Err(_) => break,
+                            Ok(Err(e)) => log::error!("Kafka error: {}", e),
+                            Ok(Ok(message)) => messages.push(message.detach()),
+                        },
+                    }
+                }
+                let events: Vec<QueueEvent> = messages.iter().map(queue_event).collect();
+                let results = hold_after_retry(&events, process_batch(&events, &retry_policy, &shutdown).await);
+                let mut is_processed: Vec<bool> = Vec::with_capacity(results.len());
+                //  Partitions where the dead-letter topic failed, they are rewound like a retried event
+                let mut failed_partitions: HashSet<(&str, i32)> = HashSet::new();
+                for ((message, event), result) in messages.iter().zip(&events).zip(results) {
+                    let partition = (event.topic.as_str(), event.partition);
+                    let is_event_processed = !failed_partitions.contains(&partition) && match result {
+                        EventResult::Processed => true,
+                        EventResult::Retry => false,
+                        //  Retried while the dead-letter topic is not available
+                        EventResult::DeadLetter(error) => match publish_dead_letter(message, &error, event.dead_letter_reason()).await {
+                            Ok(_) => true,
+                            Err(publish_error) => {
+                                log::error!("Dead letter error: {}", publish_error);
+                                false
                             }
-						} else if topic == "form" {
-							let _document = match serde_json::from_str(payload) {
-								Ok(value) => value,
-								Err(error) => {
-									log::warn!("{}", error);
-									FormDocument {
-										document: None
-									}
-								},
-							};
-							if _document.document.is_some() {
-								let _form_document: &dyn IndexDocument = &(_document.document.unwrap());
-								match process_index(event_type, _form_document).await {
-									Ok(_) => consumer.commit_message(&message, CommitMode::Async).unwrap(),
-									Err(error) => log::warn!("{}", error)
-								}
-							}
-                        }
-                        // TODO: Add token header
-                        // if let Some(headers) = message.headers() {
-                        //     for header in headers.iter() {
-                        //         log::info!("  Header {:#?}: {:?}", header.key, header.value);
-                        //     }
-                        // }
+                        },
+                    };
+                    if !is_event_processed {
+                        failed_partitions.insert(partition);
                     }
-                };
+                    is_processed.push(is_event_processed);
+                }
+                if partition_offsets.commit_batch(&consumer, &events, &is_processed) && !*shutdown.borrow() {
+                    //  OpenSearch is still failing, wait before read the rewound events.
+                    //  The change could be already seen by the batch, `wait_for` checks the current value
+                    let mut backoff_shutdown = shutdown.clone();
+                    tokio::select! {
+                        _ = backoff_shutdown.wait_for(|is_shutdown| *is_shutdown) => {},
+                        _ = tokio::time::sleep(retry_policy.maximum_backoff) => {},
+                    }
+                }
             }
+            partition_offsets.flush(&consumer);
+            consumer.unsubscribe();
+            log::info!("Kafka consumer closed");
         },
         Err(error) => log::error!("Consume Queue Error {}", error),
     };
 }
 
-async fn process_index(_event_type: String, _document: &dyn IndexDocument) -> Result<bool, std::string::String> {
-    if _event_type.eq("new") {
-        match create(_document).await {
-            Ok(_) => return Ok(true),
-            Err(error) => return Err(error.to_string())
-        };  
-    } else if _event_type.eq("update") {
-        match delete(_document).await {
-            Ok(_) => {
-                match create(_document).await {
-                    Ok(_) => return Ok(true),
-                    Err(error) => return Err(error.to_string())
-                }
+/// Message of the queue with its document
+struct QueueEvent {
+    topic: String,
+    partition: i32,
+    offset: i64,
+    /// Envelope of the event, or the event type of a legacy message
+    metadata: EventMetadata,
+    /// Document of the event, an error when the payload does not match the model of the topic
+    document: Result<Option<Box<dyn IndexDocument + Send>>, String>,
+    /// Produced time of the event or timestamp of the message, used as external version so reordered events can not lose data
+    version: Option<i64>,
+    /// The message has a valid token of the producer
+    is_authenticated: bool,
+}
+
+impl QueueEvent {
+    fn dead_letter_reason(&self) -> DeadLetterReason {
+        if self.is_authenticated {
+            DeadLetterReason::Processing
+        } else {
+            DeadLetterReason::Authentication
+        }
+    }
+}
+
+fn queue_event(message: &OwnedMessage) -> QueueEvent {
+    let payload = match message.payload_view::<str>() {
+        None => "",
+        Some(Ok(s)) => s,
+        Some(Err(e)) => {
+            log::info!("Error while deserializing message payload: {:?}", e);
+            ""
+        }
+    };
+    let key = match message.key_view::<str>() {
+        None => "",
+        Some(Ok(s)) => s,
+        Some(Err(e)) => {
+            log::info!("Error while deserializing message key: {:?}", e);
+            ""
+        }
+    };
+    let topic = message.topic();
+    let headers = MessageHeaders::from_message(message);
+    if let Err(error) = headers.authenticate() {
+        log::warn!("Message {} {} {} unauthenticated: {}", topic, message.partition(), message.offset(), error);
+        return QueueEvent {
+            topic: topic.to_owned(),
+            partition: message.partition(),
+            offset: message.offset(),
+            metadata: EventMetadata::default(),
+            document: match auth_failure_action() {
+                AuthFailureAction::DeadLetter => Err(error),
+                AuthFailureAction::Reject => Ok(None),
+            },
+            version: None,
+            is_authenticated: false,
+        };
+    }
+    //  The event type of the envelope is used before the header, the key is the last fallback
+    let event_type = headers.event_type.as_deref().unwrap_or(key);
+    let (metadata, document) = match headers.validate_content_type().and_then(|_| event_metadata(event_type, payload)) {
+        Ok(metadata) => (metadata, topic_document(topic, payload).and_then(|document| routed_document(topic, document, &headers))),
+        Err(error) => (EventMetadata::default(), Err(error)),
+    };
+    let version = metadata.produced_at.or(message.timestamp().to_millis());
+    QueueEvent {
+        topic: topic.to_owned(),
+        partition: message.partition(),
+        offset: message.offset(),
+        metadata,
+        document,
+        version,
+        is_authenticated: true,
+    }
+}
+
+/// Document written on the index of the `x-target-index` header when the message has it,
+/// only an index of the resource of the topic is accepted
+fn routed_document(_topic: &str, _document: Option<Box<dyn IndexDocument + Send>>, _headers: &MessageHeaders) -> Result<Option<Box<dyn IndexDocument + Send>>, String> {
+    let target_index = _headers.target_index(topic_index_prefix(_topic))?;
+    Ok(match (_document, target_index) {
+        (Some(document), Some(index_name)) => Some(Box::new(RoutedDocument {
+            document,
+            index_name,
+        })),
+        (document, _) => document,
+    })
+}
+
+/// Outcome of an event after the batch is written
+#[derive(Debug, PartialEq)]
+enum EventResult {
+    Processed,
+    /// OpenSearch is still failing, read it again
+    Retry,
+    /// The event can not be processed, it is sent to the dead-letter topic with the error
+    DeadLetter(String),
+}
+
+/// Attempts and initial backoff to write a batch when OpenSearch fails with a transient error
+struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+    maximum_backoff: Duration,
+}
+
+impl RetryPolicy {
+    fn from_env() -> Self {
+        let attempts: u32 = match env::var("KAFKA_RETRY_ATTEMPTS") {
+            Ok(value) => value.trim().parse::<u32>().unwrap_or_else(|_| {
+                log::error!("Variable `KAFKA_RETRY_ATTEMPTS` is invalid, as default 5");
+                5
+            }),
+            Err(_) => {
+                log::info!("Variable `KAFKA_RETRY_ATTEMPTS` Not found from enviroment, as default 5");
+                5
+            }
+        };
+        let backoff: u64 = match env::var("KAFKA_RETRY_BACKOFF_MS") {
+            Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+                log::error!("Variable `KAFKA_RETRY_BACKOFF_MS` is invalid, as default 500");
+                500
+            }),
+            Err(_) => {
+                log::info!("Variable `KAFKA_RETRY_BACKOFF_MS` Not found from enviroment, as default 500");
+                500
+            }
+        };
+        RetryPolicy {
+            attempts,
+            backoff: Duration::from_millis(backoff),
+            maximum_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Exponential backoff of the attempt, starting at `0`
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2_u32.saturating_pow(attempt))
+            .min(self.maximum_backoff)
+    }
+}
+
+/// Write the events with bulk requests, retrying the transient failures, returns the result of each event.
+/// Events without document or with unknown type are skipped as processed, the retries stop on shutdown
+async fn process_batch(_events: &[QueueEvent], _retry_policy: &RetryPolicy, _shutdown: &watch::Receiver<bool>) -> Vec<EventResult> {
+    let mut results: Vec<EventResult> = _events.iter().map(|_| EventResult::Processed).collect();
+    let mut pending = pending_operations(_events, &mut results);
+    let mut attempt: u32 = 0;
+    while !pending.is_empty() {
+        let operations: Vec<BulkOperation> = pending.iter()
+            .filter_map(|(position, action)| {
+                let event = &_events[*position];
+                event.document.as_ref().ok().and_then(|document| document.as_ref()).map(|document| BulkOperation {
+                    action: *action,
+                    document: document.as_ref(),
+                    version: event.version,
+                })
+            })
+            .collect();
+        let failed = failed_operations(&pending, bulk(&operations).await, &mut results);
+        //  Evict the cached responses even if the write fails, the index could be changed
+        for operation in &operations {
+            evict_response(&operation.document.index_name(), &operation.document.id());
+        }
+        if failed.is_empty() {
+            break;
+        }
+        let is_stopped = if attempt >= _retry_policy.attempts || *_shutdown.borrow() {
+            true
+        } else {
+            let backoff = _retry_policy.backoff(attempt);
+            log::warn!("Retrying {} records in {:?}, attempt {} of {}", failed.len(), backoff, attempt + 1, _retry_policy.attempts);
+            let mut shutdown = _shutdown.clone();
+            tokio::select! {
+                _ = shutdown.wait_for(|is_shutdown| *is_shutdown) => true,
+                _ = tokio::time::sleep(backoff) => false,
+            }
+        };
+        //  Without attempts or on shutdown the failed events are consumed again
+        if is_stopped {
+            for (position, _) in &failed {
+                results[*position] = EventResult::Retry;
+            }
+            break;
+        }
+        attempt += 1;
+        pending = failed;
+    }
+    for (event, result) in _events.iter().zip(&results) {
+        if let (Some(event_id), EventResult::Processed) = (&event.metadata.event_id, result) {
+            mark_processed_event(event_id);
+        }
+    }
+    results
+}
+
+/// Partitions are rewound to its first retried event, the following events are read again, so they are
+/// retried too instead of being dead-lettered until the partition moves past the failed event
+fn hold_after_retry(_events: &[QueueEvent], _results: Vec<EventResult>) -> Vec<EventResult> {
+    let mut retried_partitions: HashSet<(&str, i32)> = HashSet::new();
+    _events.iter().zip(_results)
+        .map(|(event, result)| {
+            let partition = (event.topic.as_str(), event.partition);
+            if retried_partitions.contains(&partition) {
+                return EventResult::Retry;
+            }
+            if result == EventResult::Retry {
+                retried_partitions.insert(partition);
+            }
+            result
+        })
+        .collect()
+}
+
+/// Operations of the events to write, redelivered events are skipped and invalid documents are dead-lettered
+fn pending_operations(_events: &[QueueEvent], _results: &mut [EventResult]) -> Vec<(usize, BulkAction)> {
+    let mut pending: Vec<(usize, BulkAction)> = Vec::new();
+    let mut batch_event_ids: HashSet<&str> = HashSet::new();
+    for (position, event) in _events.iter().enumerate() {
+        //  Redelivered events are written once
+        if let Some(event_id) = &event.metadata.event_id {
+            if is_processed_event(event_id) || !batch_event_ids.insert(event_id) {
+                log::info!("Event {:?} of {:?} already processed, skipped", event_id, event.metadata.source);
+                continue;
+            }
+        }
+        match &event.document {
+            Ok(Some(_)) => {},
+            Ok(None) => continue,
+            Err(error) => {
+                _results[position] = EventResult::DeadLetter(format!("Invalid {} message: {}", event.topic, error));
+                continue;
             },
-            Err(error) => return Err(error.to_string())
+        }
+        if event.metadata.event_type.eq("new") || event.metadata.event_type.eq("update") {
+            pending.push((position, BulkAction::Index));
+        } else if event.metadata.event_type.eq("delete") {
+            pending.push((position, BulkAction::Delete));
+        }
+    }
+    pending
+}
+
+/// Operations to retry by the result of the bulk request, the rejected operations are dead-lettered
+fn failed_operations(_pending: &[(usize, BulkAction)], _bulk_results: Result<Vec<Result<bool, DictionaryError>>, DictionaryError>, _results: &mut [EventResult]) -> Vec<(usize, BulkAction)> {
+    let mut failed: Vec<(usize, BulkAction)> = Vec::new();
+    match _bulk_results {
+        Ok(operation_results) => {
+            for (pending_operation, result) in _pending.iter().zip(operation_results) {
+                match result {
+                    Ok(_) => {},
+                    Err(error) if error.is_transient() => {
+                        log::warn!("{}", error);
+                        failed.push(*pending_operation);
+                    },
+                    Err(error) => _results[pending_operation.0] = EventResult::DeadLetter(error.to_string()),
+                }
+            }
+        },
+        Err(error) if error.is_transient() => {
+            log::warn!("{}", error);
+            failed = _pending.to_owned();
+        },
+        Err(error) => {
+            for (position, _) in _pending {
+                _results[*position] = EventResult::DeadLetter(error.to_string());
+            }
+        },
+    }
+    failed
+}
+
+/// Next offset to commit of each partition, a partition with a failed event is rewound to it
+#[derive(Default)]
+struct PartitionOffsets {
+    committed: HashMap<(String, i32), i64>,
+}
+
+/// Offsets of a batch by partition: the offset after the last event processed without a previous failure,
+/// and the offset of the first failed event
+#[derive(Debug, Default)]
+struct BatchOffsets {
+    offsets: HashMap<(String, i32), i64>,
+    failed_partitions: HashMap<(String, i32), i64>,
+}
+
+fn batch_offsets(_events: &[QueueEvent], _results: &[bool]) -> BatchOffsets {
+    let mut batch_offsets = BatchOffsets::default();
+    for (event, is_processed) in _events.iter().zip(_results) {
+        let partition = (event.topic.to_owned(), event.partition);
+        if batch_offsets.failed_partitions.contains_key(&partition) {
+            continue;
+        }
+        if !is_processed {
+            batch_offsets.failed_partitions.insert(partition, event.offset);
+            continue;
+        }
+        batch_offsets.offsets.insert(partition, event.offset + 1);
+    }
+    batch_offsets
+}
+
+/// Partitions currently assigned to the consumer, `None` when the assignment can not be read
+fn assigned_partitions<C: Consumer<X>, X: ConsumerContext>(_consumer: &C) -> Option<HashSet<(String, i32)>> {
+    match _consumer.assignment() {
+        Ok(assignment) => Some(assignment.elements().iter()
+            .map(|partition| (partition.topic().to_owned(), partition.partition()))
+            .collect()),
+        Err(error) => {
+            log::error!("Kafka assignment error: {}", error);
+            None
+        },
+    }
+}
+
+impl PartitionOffsets {
+    /// Forget the partitions revoked by a rebalance, their offsets are committed by their new owner
+    fn retain_assigned(&mut self, _assigned: &HashSet<(String, i32)>) {
+        self.committed.retain(|partition, _| _assigned.contains(partition));
+    }
+
+    /// Commit by partition the offset after the last event processed without a previous failure,
+    /// returns `true` when some partition was rewound to retry it. Partitions revoked while the batch
+    /// was processed are not committed
+    fn commit_batch<C: Consumer<X>, X: ConsumerContext>(&mut self, _consumer: &C, _events: &[QueueEvent], _results: &[bool]) -> bool {
+        let BatchOffsets { mut offsets, failed_partitions } = batch_offsets(_events, _results);
+        let assigned = match assigned_partitions(_consumer) {
+            Some(assigned) => assigned,
+            None => return !failed_partitions.is_empty(),
         };
-    } else if _event_type.eq("delete") {
-        match delete(_document).await {
-            Ok(_) => return Ok(true),
-            Err(error) => return Err(error.to_string())
+        self.retain_assigned(&assigned);
+        offsets.retain(|partition, _| assigned.contains(partition));
+        //  Read again from the first failed event, the following events are rewritten with the same version
+        for ((topic, partition), offset) in &failed_partitions {
+            if !assigned.contains(&(topic.to_owned(), *partition)) {
+                continue;
+            }
+            log::warn!("Kafka partition {} {} rewound to offset {}", topic, partition, offset);
+            if let Err(error) = _consumer.seek(topic, *partition, Offset::Offset(*offset), Duration::from_secs(10)) {
+                log::error!("Kafka seek error: {}", error);
+            }
+        }
+        let mut partition_list = TopicPartitionList::new();
+        for (partition, offset) in &offsets {
+            if self.committed.get(partition) == Some(offset) {
+                continue;
+            }
+            if let Err(error) = partition_list.add_partition_offset(&partition.0, partition.1, Offset::Offset(*offset)) {
+                log::error!("Kafka offset error: {}", error);
+            }
+        }
+        if partition_list.count() > 0 {
+            match _consumer.commit(&partition_list, CommitMode::Async) {
+                Ok(_) => self.committed.extend(offsets),
+                Err(error) => log::error!("Kafka commit error: {}", error),
+            }
+        }
+        !failed_partitions.is_empty()
+    }
+
+    /// Commit the offsets of the assigned partitions synchronously before close the consumer, the
+    /// asynchronous commits could be pending
+    fn flush<C: Consumer<X>, X: ConsumerContext>(&mut self, _consumer: &C) {
+        match assigned_partitions(_consumer) {
+            Some(assigned) => self.retain_assigned(&assigned),
+            None => return,
+        }
+        let mut partition_list = TopicPartitionList::new();
+        for ((topic, partition), offset) in &self.committed {
+            if let Err(error) = partition_list.add_partition_offset(topic, *partition, Offset::Offset(*offset)) {
+                log::error!("Kafka offset error: {}", error);
+            }
+        }
+        if partition_list.count() == 0 {
+            return;
+        }
+        match _consumer.commit(&partition_list, CommitMode::Sync) {
+            Ok(_) => log::info!("Kafka offsets committed: {:?}", self.committed),
+            Err(error) => log::error!("Kafka commit error: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(_topic: &str, _partition: i32, _offset: i64) -> QueueEvent {
+        QueueEvent {
+            topic: _topic.to_owned(),
+            partition: _partition,
+            offset: _offset,
+            metadata: EventMetadata::default(),
+            document: Ok(None),
+            version: None,
+            is_authenticated: true,
+        }
+    }
+
+    fn partition(_topic: &str, _partition: i32) -> (String, i32) {
+        (_topic.to_owned(), _partition)
+    }
+
+    fn document_event(_offset: i64, _event_type: &str, _event_id: Option<&str>) -> QueueEvent {
+        QueueEvent {
+            metadata: EventMetadata {
+                event_id: _event_id.map(|event_id| event_id.to_owned()),
+                event_type: _event_type.to_owned(),
+                ..Default::default()
+            },
+            document: Ok(Some(Box::new(Menu::with_index(Some(1), "menu".to_owned())))),
+            ..event("menu", 0, _offset)
+        }
+    }
+
+    fn processed_results(_events: &[QueueEvent]) -> Vec<EventResult> {
+        _events.iter().map(|_| EventResult::Processed).collect()
+    }
+
+    #[test]
+    fn pending_operations_by_event_type() {
+        let events = vec![
+            document_event(0, "new", None),
+            document_event(1, "update", None),
+            document_event(2, "delete", None),
+            document_event(3, "unknown", None),
+            event("menu", 0, 4),
+        ];
+        let mut results = processed_results(&events);
+        let pending = pending_operations(&events, &mut results);
+        assert_eq!(pending, vec![(0, BulkAction::Index), (1, BulkAction::Index), (2, BulkAction::Delete)]);
+        assert!(results.iter().all(|result| *result == EventResult::Processed));
+    }
+
+    #[test]
+    fn redelivered_events_are_written_once() {
+        mark_processed_event("test-processed");
+        let events = vec![
+            document_event(0, "new", Some("test-processed")),
+            document_event(1, "new", Some("test-batch")),
+            document_event(2, "update", Some("test-batch")),
+        ];
+        let mut results = processed_results(&events);
+        assert_eq!(pending_operations(&events, &mut results), vec![(1, BulkAction::Index)]);
+    }
+
+    #[test]
+    fn transient_bulk_failures_are_retried() {
+        let pending = vec![(0, BulkAction::Index), (1, BulkAction::Index), (2, BulkAction::Delete)];
+        let mut results: Vec<EventResult> = (0..3).map(|_| EventResult::Processed).collect();
+        let bulk_results = Ok(vec![
+            Ok(true),
+            Err(DictionaryError::OpenSearch("es_rejected_execution_exception".to_owned())),
+            Ok(false),
+        ]);
+        assert_eq!(failed_operations(&pending, bulk_results, &mut results), vec![(1, BulkAction::Index)]);
+        assert!(results.iter().all(|result| *result == EventResult::Processed));
+        let failed = failed_operations(&pending, Err(DictionaryError::OpenSearch("unavailable".to_owned())), &mut results);
+        assert_eq!(failed, pending);
+    }
+
+    #[test]
+    fn invalid_documents_are_dead_lettered() {
+        let mut invalid_event = document_event(1, "new", None);
+        invalid_event.document = Err("document without id".to_owned());
+        let events = vec![document_event(0, "new", None), invalid_event];
+        let mut results = processed_results(&events);
+        assert_eq!(pending_operations(&events, &mut results), vec![(0, BulkAction::Index)]);
+        assert_eq!(results[1], EventResult::DeadLetter("Invalid menu message: document without id".to_owned()));
+    }
+
+    #[test]
+    fn rejected_bulk_operations_are_dead_lettered() {
+        let pending = vec![(0, BulkAction::Index), (1, BulkAction::Index)];
+        let mut results: Vec<EventResult> = (0..2).map(|_| EventResult::Processed).collect();
+        let bulk_results = Ok(vec![Err(DictionaryError::DocumentRejected("mapper_parsing_exception".to_owned())), Ok(true)]);
+        assert!(failed_operations(&pending, bulk_results, &mut results).is_empty());
+        assert!(matches!(results[0], EventResult::DeadLetter(_)));
+        assert_eq!(results[1], EventResult::Processed);
+        let failed = failed_operations(&pending, Err(DictionaryError::Deserialization("invalid response".to_owned())), &mut results);
+        assert!(failed.is_empty());
+        assert!(results.iter().all(|result| matches!(result, EventResult::DeadLetter(_))));
+    }
+
+    #[test]
+    fn events_after_a_retry_of_the_partition_are_not_dead_lettered() {
+        let events = vec![event("menu", 0, 10), event("menu", 0, 11), event("menu", 0, 12), event("menu", 1, 5), event("window", 0, 3)];
+        let results = hold_after_retry(&events, vec![
+            EventResult::DeadLetter("invalid".to_owned()),
+            EventResult::Retry,
+            EventResult::DeadLetter("invalid".to_owned()),
+            EventResult::DeadLetter("invalid".to_owned()),
+            EventResult::Processed,
+        ]);
+        assert_eq!(results, vec![
+            EventResult::DeadLetter("invalid".to_owned()),
+            EventResult::Retry,
+            EventResult::Retry,
+            EventResult::DeadLetter("invalid".to_owned()),
+            EventResult::Processed,
+        ]);
+    }
+
+    #[test]
+    fn retry_backoff_is_exponential_until_the_maximum() {
+        let retry_policy = RetryPolicy {
+            attempts: 10,
+            backoff: Duration::from_millis(500),
+            maximum_backoff: Duration::from_secs(30),
         };
+        assert_eq!(retry_policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(retry_policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(retry_policy.backoff(7), Duration::from_secs(30));
+        assert_eq!(retry_policy.backoff(40), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn batch_offsets_after_the_last_processed_event() {
+        let events = vec![event("menu", 0, 10), event("menu", 0, 11), event("menu", 1, 5)];
+        let batch = batch_offsets(&events, &[true, true, true]);
+        assert_eq!(batch.offsets, HashMap::from([(partition("menu", 0), 12), (partition("menu", 1), 6)]));
+        assert!(batch.failed_partitions.is_empty());
+    }
+
+    #[test]
+    fn batch_offsets_stop_on_the_first_failed_event() {
+        let events = vec![event("menu", 0, 10), event("menu", 0, 11), event("menu", 0, 12), event("window", 0, 3)];
+        let batch = batch_offsets(&events, &[true, false, true, true]);
+        assert_eq!(batch.offsets, HashMap::from([(partition("menu", 0), 11), (partition("window", 0), 4)]));
+        assert_eq!(batch.failed_partitions, HashMap::from([(partition("menu", 0), 11)]));
     }
-    Ok(true)
-}
\ No newline at end of file
+
+    #[test]
+    fn batch_offsets_of_a_partition_failed_at_first_event() {
+        let batch = batch_offsets(&[event("menu", 0, 10), event("menu", 0, 11)], &[false, true]);
+        assert!(batch.offsets.is_empty());
+        assert_eq!(batch.failed_partitions, HashMap::from([(partition("menu", 0), 10)]));
+    }
+
+    #[test]
+    fn revoked_partitions_are_pruned() {
+        let mut partition_offsets = PartitionOffsets::default();
+        partition_offsets.committed.insert(partition("menu", 0), 10);
+        partition_offsets.committed.insert(partition("menu", 1), 20);
+        partition_offsets.retain_assigned(&HashSet::from([partition("menu", 1), partition("window", 0)]));
+        assert_eq!(partition_offsets.committed, HashMap::from([(partition("menu", 1), 20)]));
+    }
+}
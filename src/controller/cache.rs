@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// In-process map where each entry expires after a time to live
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    time_to_live: Duration,
+    entries: Mutex<HashMap<K, (V, Instant)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    pub fn new(time_to_live: Duration) -> Self {
+        TtlCache {
+            time_to_live,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.time_to_live.is_zero()
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut entries = self.entries.lock().ok()?;
+        match entries.get(key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            },
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            let now = Instant::now();
+            entries.retain(|_, (_, expires_at)| *expires_at > now);
+            entries.insert(key, (value, now + self.time_to_live));
+        }
+    }
+
+    pub fn remove(&self, key: &K) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(key);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// Seconds that index existence and index resolution results are cached, `0` disable it
+pub fn index_cache_ttl() -> Duration {
+    static INDEX_CACHE_TTL: OnceLock<Duration> = OnceLock::new();
+    *INDEX_CACHE_TTL.get_or_init(|| {
+        let seconds: u64 = match env::var("INDEX_CACHE_TTL") {
+            Ok(value) => match value.trim().parse::<u64>() {
+                Ok(seconds) => seconds,
+                Err(error) => {
+                    log::error!("Variable `INDEX_CACHE_TTL` is invalid, as default 60 seconds: {}", error);
+                    60
+                },
+            },
+            Err(_) => {
+                log::info!("Variable `INDEX_CACHE_TTL` Not found from enviroment, as default 60 seconds");
+                60
+            },
+        };
+        Duration::from_secs(seconds)
+    })
+}
+
+/// Existence of each index by name
+pub fn index_existence_cache() -> &'static TtlCache<String, bool> {
+    static INDEX_EXISTENCE_CACHE: OnceLock<TtlCache<String, bool>> = OnceLock::new();
+    INDEX_EXISTENCE_CACHE.get_or_init(|| TtlCache::new(index_cache_ttl()))
+}
+
+static INDEX_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Changes each time an index is created or deleted, cached resolutions of older generations are stale
+pub fn index_generation() -> u64 {
+    INDEX_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Mark all cached index resolutions as stale
+pub fn invalidate_index_resolutions() {
+    INDEX_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Forget all cached results that depend on the existence of the index
+pub fn invalidate_index(_index_name: &str) {
+    index_existence_cache().remove(&_index_name.to_owned());
+    response_cache().remove_where(|(index_name, _)| index_name == _index_name);
+    invalidate_index_resolutions();
+    log::info!("Index cache invalidated by `{}`", _index_name);
+}
+
+struct LruEntry<V> {
+    value: V,
+    size: usize,
+    tick: u64,
+    expires_at: Option<Instant>,
+}
+
+struct LruState<K, V> {
+    entries: HashMap<K, LruEntry<V>>,
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    bytes: usize,
+}
+
+/// In-process map bounded by entries and bytes, the least recently used entries are evicted first.
+/// With a time to live the entries also expire
+pub struct LruCache<K, V> {
+    max_entries: usize,
+    max_bytes: usize,
+    time_to_live: Option<Duration>,
+    state: Mutex<LruState<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        LruCache {
+            max_entries,
+            max_bytes,
+            time_to_live: None,
+            state: Mutex::new(LruState {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+                bytes: 0,
+            }),
+        }
+    }
+
+    /// Entries expire after `time_to_live`, zero is without expiration
+    pub fn with_time_to_live(mut self, time_to_live: Duration) -> Self {
+        self.time_to_live = if time_to_live.is_zero() { None } else { Some(time_to_live) };
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_entries > 0 && self.max_bytes > 0
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut state = self.state.lock().ok()?;
+        let expires_at = state.entries.get(key)?.expires_at;
+        if expires_at.map(|expires_at| expires_at <= Instant::now()).unwrap_or(false) {
+            Self::remove_entry(&mut state, key);
+            return None;
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        let entry = state.entries.get_mut(key)?;
+        let previous_tick = std::mem::replace(&mut entry.tick, tick);
+        let value = entry.value.clone();
+        state.order.remove(&previous_tick);
+        state.order.insert(tick, key.clone());
+        Some(value)
+    }
+
+    /// Add an entry of `size` bytes, it is ignored when it is greater than the cache
+    pub fn insert(&self, key: K, value: V, size: usize) {
+        self.insert_if(key, value, size, || true);
+    }
+
+    /// Add an entry only when the predicate is true, it is checked while the cache is locked, so a
+    /// removal done after the predicate changes is never lost
+    pub fn insert_if<F: FnOnce() -> bool>(&self, key: K, value: V, size: usize, predicate: F) {
+        if !self.is_enabled() || size > self.max_bytes {
+            return;
+        }
+        if let Ok(mut state) = self.state.lock() {
+            if !predicate() {
+                return;
+            }
+            Self::remove_entry(&mut state, &key);
+            state.tick += 1;
+            let tick = state.tick;
+            let expires_at = self.time_to_live.map(|time_to_live| Instant::now() + time_to_live);
+            state.order.insert(tick, key.clone());
+            state.entries.insert(key, LruEntry { value, size, tick, expires_at });
+            state.bytes += size;
+            while state.entries.len() > self.max_entries || state.bytes > self.max_bytes {
+                let oldest_key = match state.order.values().next() {
+                    Some(oldest_key) => oldest_key.clone(),
+                    None => break,
+                };
+                Self::remove_entry(&mut state, &oldest_key);
+            }
+        }
+    }
+
+    pub fn remove(&self, key: &K) {
+        if let Ok(mut state) = self.state.lock() {
+            Self::remove_entry(&mut state, key);
+        }
+    }
+
+    /// Remove all entries whose key matches
+    pub fn remove_where<F: Fn(&K) -> bool>(&self, predicate: F) {
+        if let Ok(mut state) = self.state.lock() {
+            let keys: Vec<K> = state.entries.keys().filter(|key| predicate(key)).cloned().collect();
+            for key in keys {
+                Self::remove_entry(&mut state, &key);
+            }
+        }
+    }
+
+    fn remove_entry(state: &mut LruState<K, V>, key: &K) {
+        if let Some(entry) = state.entries.remove(key) {
+            state.order.remove(&entry.tick);
+            state.bytes -= entry.size;
+        }
+    }
+}
+
+pub(crate) fn usize_env_var(_name: &str, _default: usize) -> usize {
+    match env::var(_name) {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(number) => number,
+            Err(error) => {
+                log::error!("Variable `{}` is invalid, as default {}: {}", _name, _default, error);
+                _default
+            },
+        },
+        Err(_) => {
+            log::info!("Variable `{}` Not found from enviroment, as default {}", _name, _default);
+            _default
+        },
+    }
+}
+
+/// Index name and document id of a cached response
+pub type ResponseCacheKey = (String, String);
+
+/// Serialized documents read by id, bounded by `RESPONSE_CACHE_MAX_ENTRIES` and `RESPONSE_CACHE_MAX_BYTES`,
+/// they expire after `RESPONSE_CACHE_TTL` seconds because other instances also write the documents
+pub fn response_cache() -> &'static LruCache<ResponseCacheKey, String> {
+    static RESPONSE_CACHE: OnceLock<LruCache<ResponseCacheKey, String>> = OnceLock::new();
+    RESPONSE_CACHE.get_or_init(|| {
+        LruCache::new(
+            usize_env_var("RESPONSE_CACHE_MAX_ENTRIES", 0),
+            usize_env_var("RESPONSE_CACHE_MAX_BYTES", 64 * 1024 * 1024),
+        )
+        .with_time_to_live(Duration::from_secs(usize_env_var("RESPONSE_CACHE_TTL", 60) as u64))
+    })
+}
+
+const RESPONSE_GENERATIONS: usize = 1024;
+
+/// Writes of the documents, by hash of the key, a response read before a write of its key is not cached
+static RESPONSE_GENERATION: [AtomicU64; RESPONSE_GENERATIONS] = [const { AtomicU64::new(0) }; RESPONSE_GENERATIONS];
+
+fn response_generation_of(_key: &ResponseCacheKey) -> &'static AtomicU64 {
+    let mut hasher = DefaultHasher::new();
+    _key.hash(&mut hasher);
+    &RESPONSE_GENERATION[(hasher.finish() % RESPONSE_GENERATIONS as u64) as usize]
+}
+
+/// Generation of the key, read before get the document from OpenSearch
+pub fn response_generation(_key: &ResponseCacheKey) -> u64 {
+    response_generation_of(_key).load(Ordering::SeqCst)
+}
+
+/// Cache the response only when its key was not written since `_generation` was read
+pub fn insert_response(_key: ResponseCacheKey, _response: String, _generation: u64) {
+    let size = _response.len();
+    let generation = response_generation_of(&_key);
+    response_cache().insert_if(_key, _response, size, || generation.load(Ordering::SeqCst) == _generation);
+}
+
+/// Forget the cached response of a document after it is written or deleted
+pub fn evict_response(_index_name: &str, _id: &str) {
+    let key = (_index_name.to_owned(), _id.to_owned());
+    response_generation_of(&key).fetch_add(1, Ordering::SeqCst);
+    response_cache().remove(&key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn least_recently_used_entries_are_evicted() {
+        let cache: LruCache<&str, i32> = LruCache::new(2, usize::MAX);
+        cache.insert("a", 1, 1);
+        cache.insert("b", 2, 1);
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.insert("c", 3, 1);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+    }
+
+    #[test]
+    fn entries_are_bounded_by_bytes() {
+        let cache: LruCache<&str, i32> = LruCache::new(10, 10);
+        cache.insert("large", 1, 11);
+        assert_eq!(cache.get(&"large"), None);
+        cache.insert("a", 1, 6);
+        cache.insert("b", 2, 6);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"b"), Some(2));
+        //  Replacing an entry releases its bytes
+        cache.insert("b", 3, 10);
+        assert_eq!(cache.get(&"b"), Some(3));
+    }
+
+    #[test]
+    fn disabled_cache_is_empty() {
+        let cache: LruCache<&str, i32> = LruCache::new(0, usize::MAX);
+        cache.insert("a", 1, 1);
+        assert!(!cache.is_enabled());
+        assert_eq!(cache.get(&"a"), None);
+    }
+
+    #[test]
+    fn entries_are_removed() {
+        let cache: LruCache<(String, String), i32> = LruCache::new(10, usize::MAX);
+        cache.insert(("menu".to_owned(), "1".to_owned()), 1, 1);
+        cache.insert(("menu".to_owned(), "2".to_owned()), 2, 1);
+        cache.insert(("window".to_owned(), "1".to_owned()), 3, 1);
+        cache.remove(&("menu".to_owned(), "1".to_owned()));
+        assert_eq!(cache.get(&("menu".to_owned(), "1".to_owned())), None);
+        cache.remove_where(|(index_name, _)| index_name == "menu");
+        assert_eq!(cache.get(&("menu".to_owned(), "2".to_owned())), None);
+        assert_eq!(cache.get(&("window".to_owned(), "1".to_owned())), Some(3));
+    }
+
+    #[test]
+    fn entries_expire() {
+        let cache: LruCache<&str, i32> = LruCache::new(10, usize::MAX).with_time_to_live(Duration::from_millis(20));
+        cache.insert("a", 1, 1);
+        assert_eq!(cache.get(&"a"), Some(1));
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get(&"a"), None);
+    }
+
+    #[test]
+    fn insert_if_checks_the_predicate() {
+        let cache: LruCache<&str, i32> = LruCache::new(10, usize::MAX);
+        cache.insert_if("a", 1, 1, || false);
+        assert_eq!(cache.get(&"a"), None);
+        cache.insert_if("a", 1, 1, || true);
+        assert_eq!(cache.get(&"a"), Some(1));
+    }
+}
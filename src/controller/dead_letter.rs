@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::message::{Header, Headers, Message, OwnedHeaders, OwnedMessage};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{Offset, TopicPartitionList};
+use serde::Serialize;
+
+use crate::controller::headers::AUTHORIZATION_HEADER;
+use crate::controller::kafka::{client_config, kafka_host};
+use crate::error::DictionaryError;
+
+/// Headers added to the dead-lettered message, the original headers are kept
+pub const ERROR_HEADER: &str = "x-dead-letter-error";
+pub const TOPIC_HEADER: &str = "x-dead-letter-topic";
+pub const PARTITION_HEADER: &str = "x-dead-letter-partition";
+pub const OFFSET_HEADER: &str = "x-dead-letter-offset";
+/// Timestamp of the original message in milliseconds, it is the external version of the document
+pub const TIMESTAMP_HEADER: &str = "x-dead-letter-timestamp";
+/// `authentication` or `processing`
+pub const REASON_HEADER: &str = "x-dead-letter-reason";
+
+/// Why a message was dead-lettered, messages without a valid token can not be replayed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeadLetterReason {
+    Authentication,
+    Processing,
+}
+
+impl DeadLetterReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeadLetterReason::Authentication => "authentication",
+            DeadLetterReason::Processing => "processing",
+        }
+    }
+}
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Topic for messages that can not be processed from `KAFKA_DEAD_LETTER_TOPIC`, empty disable it
+pub fn dead_letter_topic() -> Option<&'static str> {
+    static DEAD_LETTER_TOPIC: OnceLock<String> = OnceLock::new();
+    let topic = DEAD_LETTER_TOPIC.get_or_init(|| {
+        match env::var("KAFKA_DEAD_LETTER_TOPIC") {
+            Ok(value) => value.trim().to_owned(),
+            Err(_) => {
+                log::info!("Variable `KAFKA_DEAD_LETTER_TOPIC` Not found from enviroment, as default `dictionary_dead_letter`");
+                "dictionary_dead_letter".to_owned()
+            },
+        }
+    });
+    if topic.is_empty() {
+        None
+    } else {
+        Some(topic.as_str())
+    }
+}
+
+fn producer() -> Result<&'static FutureProducer, DictionaryError> {
+    static PRODUCER: OnceLock<FutureProducer> = OnceLock::new();
+    if let Some(producer) = PRODUCER.get() {
+        return Ok(producer);
+    }
+    let producer: FutureProducer = client_config(&kafka_host(), &[("message.timeout.ms", "10000")])
+        .create()?;
+    Ok(PRODUCER.get_or_init(|| producer))
+}
+
+/// Message stored on the dead-letter topic
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub partition: i32,
+    pub offset: i64,
+    /// Topic where the message was consumed
+    pub topic: Option<String>,
+    pub error: Option<String>,
+    /// `authentication` or `processing`, `None` for dead letters published before the reason
+    pub reason: Option<String>,
+    /// Timestamp of the original message in milliseconds
+    pub timestamp: Option<i64>,
+    /// Key, payload and headers as text, without the token of the producer, the replay uses the original bytes
+    pub key: Option<String>,
+    pub payload: Option<String>,
+    pub headers: Vec<(String, String)>,
+    #[serde(skip)]
+    message: OwnedMessage,
+}
+
+/// Headers of the message in order, the token of the producer is kept to replay the message with it
+fn header_values<M: Message>(_message: &M) -> Vec<(String, Vec<u8>)> {
+    match _message.headers() {
+        Some(headers) => headers.iter()
+            .map(|header| (header.key.to_owned(), header.value.map(|value| value.to_vec()).unwrap_or_default()))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn owned_headers(_headers: &[(String, Vec<u8>)]) -> OwnedHeaders {
+    let mut headers = OwnedHeaders::new_with_capacity(_headers.len());
+    for (key, value) in _headers {
+        headers = headers.insert(Header {
+            key: key.as_str(),
+            value: Some(value),
+        });
+    }
+    headers
+}
+
+/// Publish the message to the dead-letter topic with the original key, payload and headers and the error
+pub async fn publish_dead_letter<M: Message>(_message: &M, _error: &str, _reason: DeadLetterReason) -> Result<(), DictionaryError> {
+    let topic = match dead_letter_topic() {
+        Some(topic) => topic,
+        None => {
+            log::error!("Message {} {} {} discarded: {}", _message.topic(), _message.partition(), _message.offset(), _error);
+            return Ok(());
+        },
+    };
+    let mut headers = header_values(_message);
+    headers.push((ERROR_HEADER.to_owned(), _error.as_bytes().to_vec()));
+    headers.push((REASON_HEADER.to_owned(), _reason.as_str().as_bytes().to_vec()));
+    headers.push((TOPIC_HEADER.to_owned(), _message.topic().as_bytes().to_vec()));
+    headers.push((PARTITION_HEADER.to_owned(), _message.partition().to_string().into_bytes()));
+    headers.push((OFFSET_HEADER.to_owned(), _message.offset().to_string().into_bytes()));
+    let timestamp = _message.timestamp().to_millis();
+    if let Some(timestamp) = timestamp {
+        headers.push((TIMESTAMP_HEADER.to_owned(), timestamp.to_string().into_bytes()));
+    }
+    let mut record: FutureRecord<[u8], [u8]> = FutureRecord::to(topic)
+        .headers(owned_headers(&headers));
+    if let Some(timestamp) = timestamp {
+        record = record.timestamp(timestamp);
+    }
+    if let Some(key) = _message.key() {
+        record = record.key(key);
+    }
+    if let Some(payload) = _message.payload() {
+        record = record.payload(payload);
+    }
+    match producer()?.send(record, TIMEOUT).await {
+        Ok(_) => {
+            log::warn!("Message {} {} {} sent to {}: {}", _message.topic(), _message.partition(), _message.offset(), topic, _error);
+            Ok(())
+        },
+        Err((error, _)) => Err(error.into()),
+    }
+}
+
+fn header_value(_headers: &[(String, String)], _key: &str) -> Option<String> {
+    _headers.iter()
+        .find(|(key, _)| key == _key)
+        .map(|(_, value)| value.to_owned())
+}
+
+fn dead_letter(_message: OwnedMessage) -> DeadLetter {
+    let headers: Vec<(String, String)> = header_values(&_message).into_iter()
+        .map(|(key, value)| (key, String::from_utf8_lossy(&value).to_string()))
+        .collect();
+    //  Dead letters published without the header keep the original timestamp as timestamp of the message
+    let timestamp = header_value(&headers, TIMESTAMP_HEADER)
+        .and_then(|value| value.parse::<i64>().ok())
+        .or_else(|| _message.timestamp().to_millis());
+    DeadLetter {
+        partition: _message.partition(),
+        offset: _message.offset(),
+        topic: header_value(&headers, TOPIC_HEADER),
+        error: header_value(&headers, ERROR_HEADER),
+        reason: header_value(&headers, REASON_HEADER),
+        timestamp,
+        key: _message.key().map(|key| String::from_utf8_lossy(key).to_string()),
+        payload: _message.payload().map(|payload| String::from_utf8_lossy(payload).to_string()),
+        headers: headers.into_iter()
+            .filter(|(key, _)| !key.starts_with("x-dead-letter-") && !key.eq_ignore_ascii_case(AUTHORIZATION_HEADER))
+            .collect(),
+        message: _message,
+    }
+}
+
+/// Read messages of the dead-letter topic from the offset of each partition until the end or the limit
+fn read_dead_letters(_topic: &str, _partition: Option<i32>, _offset: Option<i64>, _limit: usize) -> Result<Vec<DeadLetter>, DictionaryError> {
+    let consumer: BaseConsumer = client_config(&kafka_host(), &[])
+        .set("group.id", "dictionary_dead_letter_reader")
+        .set("enable.auto.commit", "false")
+        .create()?;
+    let metadata = consumer.fetch_metadata(Some(_topic), TIMEOUT)?;
+    let partitions: Vec<i32> = metadata.topics().iter()
+        .flat_map(|topic| topic.partitions().iter().map(|partition| partition.id()))
+        .filter(|partition| _partition.map(|value| value == *partition).unwrap_or(true))
+        .collect();
+    let mut assignment = TopicPartitionList::new();
+    let mut pending: HashMap<i32, i64> = HashMap::new();
+    for partition in partitions {
+        let (low, high) = consumer.fetch_watermarks(_topic, partition, TIMEOUT)?;
+        let start = _offset.unwrap_or(low).max(low);
+        if start >= high {
+            continue;
+        }
+        assignment.add_partition_offset(_topic, partition, Offset::Offset(start))?;
+        pending.insert(partition, high);
+    }
+    let mut dead_letters: Vec<DeadLetter> = Vec::new();
+    if pending.is_empty() {
+        return Ok(dead_letters);
+    }
+    consumer.assign(&assignment)?;
+    let deadline = Instant::now() + TIMEOUT;
+    while !pending.is_empty() && dead_letters.len() < _limit && Instant::now() < deadline {
+        match consumer.poll(Duration::from_millis(500)) {
+            Some(Ok(message)) => {
+                if let Some(high) = pending.get(&message.partition()) {
+                    if message.offset() + 1 >= *high {
+                        pending.remove(&message.partition());
+                    }
+                }
+                dead_letters.push(dead_letter(message.detach()));
+            },
+            Some(Err(error)) => return Err(error.into()),
+            None => {},
+        }
+    }
+    Ok(dead_letters)
+}
+
+/// Messages of the dead-letter topic, from the offset of each partition
+pub async fn list_dead_letters(_partition: Option<i32>, _offset: Option<i64>, _limit: usize) -> Result<Vec<DeadLetter>, DictionaryError> {
+    let topic = match dead_letter_topic() {
+        Some(topic) => topic,
+        None => return Err(DictionaryError::InvalidParameter("Dead-letter topic is disabled".to_owned())),
+    };
+    match tokio::task::spawn_blocking(move || read_dead_letters(topic, _partition, _offset, _limit)).await {
+        Ok(result) => result,
+        Err(error) => Err(DictionaryError::Kafka(error.to_string())),
+    }
+}
+
+/// Publish again a dead-lettered message to its original topic with its original bytes, headers and timestamp,
+/// without the dead-letter headers, so the replay does not overwrite newer versions of the document. The message
+/// is authenticated again with its original token, a message without a valid token is never replayed
+pub async fn replay_dead_letter(_partition: i32, _offset: i64) -> Result<DeadLetter, DictionaryError> {
+    let dead_letters = list_dead_letters(Some(_partition), Some(_offset), 1).await?;
+    let dead_letter = match dead_letters.into_iter().find(|dead_letter| dead_letter.offset == _offset) {
+        Some(dead_letter) => dead_letter,
+        None => return Err(DictionaryError::DocumentNotFound(format!("Dead letter {} {} Not Found", _partition, _offset))),
+    };
+    let topic = match &dead_letter.topic {
+        Some(topic) => topic.to_owned(),
+        None => return Err(DictionaryError::InvalidParameter(format!("Dead letter {} {} without original topic", _partition, _offset))),
+    };
+    if dead_letter.reason.as_deref() == Some(DeadLetterReason::Authentication.as_str()) {
+        return Err(DictionaryError::InvalidParameter(format!("Dead letter {} {} failed authentication, it can not be replayed", _partition, _offset)));
+    }
+    let message = &dead_letter.message;
+    let headers: Vec<(String, Vec<u8>)> = header_values(message).into_iter()
+        .filter(|(key, _)| !key.starts_with("x-dead-letter-"))
+        .collect();
+    let mut record: FutureRecord<[u8], [u8]> = FutureRecord::to(&topic)
+        .headers(owned_headers(&headers));
+    if let Some(timestamp) = dead_letter.timestamp {
+        record = record.timestamp(timestamp);
+    }
+    if let Some(key) = message.key() {
+        record = record.key(key);
+    }
+    if let Some(payload) = message.payload() {
+        record = record.payload(payload);
+    }
+    match producer()?.send(record, TIMEOUT).await {
+        Ok(_) => {
+            log::info!("Dead letter {} {} replayed to {}", _partition, _offset, topic);
+            Ok(dead_letter)
+        },
+        Err((error, _)) => Err(error.into()),
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::OnceLock;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::controller::cache::{usize_env_var, LruCache};
+
+/// Last schema version of the envelope supported by the consumer, the legacy format is the version `0`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Client of the ADempiere instance that produced the event
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct EventTenant {
+    pub client_id: Option<i32>,
+    pub client_uuid: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Fields of the envelope, the `document` is read by the handler of the topic
+#[derive(Deserialize, Debug, Default)]
+struct EventEnvelope {
+    event_id: Option<String>,
+    event_type: Option<String>,
+    source: Option<String>,
+    /// RFC 3339 date time or milliseconds since epoch
+    produced_at: Option<Value>,
+    schema_version: Option<u32>,
+    tenant: Option<EventTenant>,
+}
+
+/// Metadata of a message, from the envelope or from the key of a legacy message
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct EventMetadata {
+    pub event_id: Option<String>,
+    /// `new`, `update` or `delete`
+    pub event_type: String,
+    pub source: Option<String>,
+    /// Milliseconds since epoch
+    pub produced_at: Option<i64>,
+    pub schema_version: u32,
+    pub tenant: Option<EventTenant>,
+}
+
+fn produced_at_millis(_value: &Value) -> Result<i64, String> {
+    match _value {
+        Value::Number(number) => number.as_i64().ok_or_else(|| format!("Invalid produced_at {}", number)),
+        Value::String(date) => DateTime::parse_from_rfc3339(date)
+            .map(|date| date.timestamp_millis())
+            .map_err(|error| format!("Invalid produced_at {:?}: {}", date, error)),
+        _ => Err(format!("Invalid produced_at {}", _value)),
+    }
+}
+
+/// Metadata of the message, a payload with `schema_version` or `event_id` is an envelope:
+/// `{"event_id", "event_type", "source", "produced_at", "schema_version", "tenant", "document"}`,
+/// otherwise it is a legacy message with the event type as key, e.g. `"new"` and `{"document"}`
+pub fn event_metadata(_key: &str, _payload: &str) -> Result<EventMetadata, String> {
+    let legacy_event_type = _key.replace("\"", "");
+    //  The handler of the topic reports the invalid payload
+    let envelope: EventEnvelope = serde_json::from_str(_payload).unwrap_or_default();
+    if envelope.schema_version.is_none() && envelope.event_id.is_none() {
+        return Ok(EventMetadata {
+            event_type: legacy_event_type,
+            ..Default::default()
+        });
+    }
+    let schema_version = envelope.schema_version.unwrap_or(SCHEMA_VERSION);
+    if schema_version > SCHEMA_VERSION {
+        return Err(format!("Unsupported schema_version {}, the last supported is {}", schema_version, SCHEMA_VERSION));
+    }
+    let produced_at = match &envelope.produced_at {
+        Some(value) => Some(produced_at_millis(value)?),
+        None => None,
+    };
+    let event_type = match envelope.event_type {
+        Some(event_type) => event_type,
+        None if !legacy_event_type.is_empty() => legacy_event_type,
+        None => return Err("Envelope without event_type".to_owned()),
+    };
+    Ok(EventMetadata {
+        event_id: envelope.event_id.filter(|event_id| !event_id.trim().is_empty()),
+        event_type,
+        source: envelope.source,
+        produced_at,
+        schema_version,
+        tenant: envelope.tenant,
+    })
+}
+
+/// Ids of the last events written, bounded by `KAFKA_EVENT_ID_CACHE_SIZE`
+fn processed_events() -> &'static LruCache<String, ()> {
+    static PROCESSED_EVENTS: OnceLock<LruCache<String, ()>> = OnceLock::new();
+    PROCESSED_EVENTS.get_or_init(|| {
+        LruCache::new(usize_env_var("KAFKA_EVENT_ID_CACHE_SIZE", 100000), usize::MAX)
+    })
+}
+
+/// The event was already written, a redelivered event is skipped
+pub fn is_processed_event(_event_id: &str) -> bool {
+    processed_events().get(&_event_id.to_owned()).is_some()
+}
+
+pub fn mark_processed_event(_event_id: &str) {
+    processed_events().insert(_event_id.to_owned(), (), _event_id.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_message_uses_the_key() {
+        let metadata = event_metadata("\"new\"", r#"{"document": {"id": 1}}"#).unwrap();
+        assert_eq!(metadata.event_type, "new");
+        assert_eq!(metadata.schema_version, 0);
+        assert!(metadata.event_id.is_none());
+        assert!(metadata.produced_at.is_none());
+    }
+
+    #[test]
+    fn envelope_is_read() {
+        let payload = r#"{
+            "event_id": "a1",
+            "event_type": "update",
+            "source": "adempiere",
+            "produced_at": "2024-01-02T03:04:05.678Z",
+            "schema_version": 1,
+            "tenant": {"client_id": 11},
+            "document": {"id": 1}
+        }"#;
+        let metadata = event_metadata("new", payload).unwrap();
+        assert_eq!(metadata.event_id.as_deref(), Some("a1"));
+        assert_eq!(metadata.event_type, "update");
+        assert_eq!(metadata.source.as_deref(), Some("adempiere"));
+        assert_eq!(metadata.produced_at, Some(1704164645678));
+        assert_eq!(metadata.schema_version, 1);
+        assert_eq!(metadata.tenant.and_then(|tenant| tenant.client_id), Some(11));
+    }
+
+    #[test]
+    fn envelope_produced_at_as_milliseconds() {
+        let metadata = event_metadata("", r#"{"event_id": "a1", "event_type": "delete", "produced_at": 1704164645678}"#).unwrap();
+        assert_eq!(metadata.produced_at, Some(1704164645678));
+        assert_eq!(metadata.schema_version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn envelope_without_event_type_uses_the_key() {
+        assert_eq!(event_metadata("delete", r#"{"event_id": "a1"}"#).unwrap().event_type, "delete");
+        assert!(event_metadata("", r#"{"event_id": "a1"}"#).is_err());
+    }
+
+    #[test]
+    fn invalid_envelopes_are_rejected() {
+        assert!(event_metadata("new", r#"{"schema_version": 2, "event_type": "new"}"#).is_err());
+        assert!(event_metadata("new", r#"{"event_id": "a1", "produced_at": "yesterday"}"#).is_err());
+        assert!(event_metadata("new", r#"{"event_id": "a1", "produced_at": true}"#).is_err());
+    }
+
+    #[test]
+    fn blank_event_id_is_ignored() {
+        assert!(event_metadata("new", r#"{"event_id": " ", "schema_version": 1}"#).unwrap().event_id.is_none());
+    }
+}
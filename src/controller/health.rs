@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::controller::kafka::{assignment_status, subscription_status};
+use crate::controller::opensearch::{cluster_health, index_templates, index_templates_registered};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+    /// Not used by the service, it does not change the readiness
+    Disabled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub details: Value,
+}
+
+impl ComponentHealth {
+    fn up(_details: Value) -> Self {
+        ComponentHealth {
+            status: HealthStatus::Up,
+            message: None,
+            details: _details,
+        }
+    }
+
+    fn down(_message: String, _details: Value) -> Self {
+        ComponentHealth {
+            status: HealthStatus::Down,
+            message: Some(_message),
+            details: _details,
+        }
+    }
+}
+
+/// Status of the service and of each component, `down` when some component is down
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthResponse {
+    pub status: HealthStatus,
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl HealthResponse {
+    fn from_components(_components: BTreeMap<String, ComponentHealth>) -> Self {
+        let is_down = _components.values().any(|component| component.status == HealthStatus::Down);
+        HealthResponse {
+            status: if is_down { HealthStatus::Down } else { HealthStatus::Up },
+            components: _components,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+}
+
+/// The process is running, it does not check the dependencies
+pub fn liveness() -> HealthResponse {
+    HealthResponse::from_components(BTreeMap::new())
+}
+
+/// Maximum lag of the consumer to be ready from `HEALTH_MAXIMUM_KAFKA_LAG`, `0` without limit
+fn maximum_kafka_lag() -> i64 {
+    static MAXIMUM_KAFKA_LAG: OnceLock<i64> = OnceLock::new();
+    *MAXIMUM_KAFKA_LAG.get_or_init(|| {
+        match env::var("HEALTH_MAXIMUM_KAFKA_LAG") {
+            Ok(value) => value.trim().parse::<i64>().unwrap_or_else(|_| {
+                log::error!("Variable `HEALTH_MAXIMUM_KAFKA_LAG` is invalid, as default 0");
+                0
+            }),
+            Err(_) => {
+                log::info!("Variable `HEALTH_MAXIMUM_KAFKA_LAG` Not found from enviroment, as default 0");
+                0
+            },
+        }
+    })
+}
+
+/// Red cluster is down, yellow is up because the replicas are optional for a single node
+async fn opensearch_health() -> ComponentHealth {
+    match cluster_health().await {
+        Ok(health) => {
+            let details = json!({
+                "cluster_name": health["cluster_name"],
+                "cluster_status": health["status"],
+                "number_of_nodes": health["number_of_nodes"],
+            });
+            match health["status"].as_str() {
+                Some("green") | Some("yellow") => ComponentHealth::up(details),
+                _ => ComponentHealth::down(format!("Cluster status {}", health["status"]), details),
+            }
+        },
+        Err(error) => ComponentHealth::down(error.to_string(), Value::Null),
+    }
+}
+
+async fn index_templates_health(_template_names: &[String]) -> ComponentHealth {
+    match index_templates("dictionary_*").await {
+        Ok(templates) => {
+            let missing: Vec<&String> = _template_names.iter()
+                .filter(|name| !templates.contains(name))
+                .collect();
+            let details = json!({
+                "registered": index_templates_registered(),
+                "missing": missing,
+            });
+            if missing.is_empty() {
+                ComponentHealth::up(details)
+            } else {
+                ComponentHealth::down(format!("{} index templates are missing", missing.len()), details)
+            }
+        },
+        Err(error) => ComponentHealth::down(error.to_string(), Value::Null),
+    }
+}
+
+/// Subscribed, with partitions assigned at least once and the lag under `HEALTH_MAXIMUM_KAFKA_LAG`
+fn kafka_health(_is_enabled: bool) -> ComponentHealth {
+    if !_is_enabled {
+        return ComponentHealth {
+            status: HealthStatus::Disabled,
+            message: None,
+            details: Value::Null,
+        };
+    }
+    let subscription = subscription_status();
+    let assignment = assignment_status();
+    let lag: Option<i64> = assignment.lag.as_ref().map(|lag| lag.values().sum());
+    let details = json!({
+        "subscription": subscription,
+        "assignment": assignment.assignment,
+        "lag": lag,
+    });
+    if subscription.state != "subscribed" {
+        let message = match &subscription.last_error {
+            Some(error) => format!("Subscription {}: {}", subscription.state, error),
+            None => format!("Subscription {}", subscription.state),
+        };
+        return ComponentHealth::down(message, details);
+    }
+    if assignment.assignment.is_none() {
+        return ComponentHealth::down("Waiting for partition assignment".to_owned(), details);
+    }
+    let maximum_lag = maximum_kafka_lag();
+    if let Some(lag) = lag {
+        if maximum_lag > 0 && lag > maximum_lag {
+            return ComponentHealth::down(format!("Lag {} is greater than {}", lag, maximum_lag), details);
+        }
+    }
+    ComponentHealth::up(details)
+}
+
+/// The service can receive requests and consume events: OpenSearch is available, the index templates
+/// exist and the consumer is subscribed with partitions assigned
+pub async fn readiness(_is_kafka_enabled: bool, _template_names: &[String]) -> HealthResponse {
+    let (opensearch, templates) = futures::join!(opensearch_health(), index_templates_health(_template_names));
+    let mut components: BTreeMap<String, ComponentHealth> = BTreeMap::new();
+    components.insert("opensearch".to_owned(), opensearch);
+    components.insert("index_templates".to_owned(), templates);
+    components.insert("kafka".to_owned(), kafka_health(_is_kafka_enabled));
+    HealthResponse::from_components(components)
+}
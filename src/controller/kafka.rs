@@ -2,14 +2,31 @@ use rdkafka::config::RDKafkaLogLevel;
 use rdkafka::consumer::stream_consumer::StreamConsumer;
 use rdkafka::consumer::{Consumer, ConsumerContext, Rebalance};
 use rdkafka::error::KafkaResult;
+use rdkafka::statistics::Statistics;
 use rdkafka::{ClientConfig, TopicPartitionList, ClientContext};
-use std::thread;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{OnceLock, RwLock};
 use std::time::Duration;
-use std::{io::Error, io::ErrorKind};
+
+use crate::error::DictionaryError;
 
 pub struct CustomContext;
 
-impl ClientContext for CustomContext {}
+impl ClientContext for CustomContext {
+    /// Lag of the assigned partitions, received each `statistics.interval.ms`
+    fn stats(&self, statistics: Statistics) {
+        let lag: HashMap<String, i64> = statistics.topics.values()
+            .flat_map(|topic| topic.partitions.values().map(move |partition| (topic.topic.to_owned(), partition)))
+            .filter(|(_, partition)| partition.partition >= 0 && partition.consumer_lag >= 0)
+            .map(|(topic, partition)| (format!("{}/{}", topic, partition.partition), partition.consumer_lag))
+            .collect();
+        if let Ok(mut state) = assignment_state().write() {
+            state.lag = Some(lag);
+        }
+    }
+}
 
 impl ConsumerContext for CustomContext {
     fn pre_rebalance(&self, rebalance: &Rebalance) {
@@ -18,6 +35,16 @@ impl ConsumerContext for CustomContext {
 
     fn post_rebalance(&self, rebalance: &Rebalance) {
         log::info!("Post rebalance {:?}", rebalance);
+        let assignment: Vec<String> = match rebalance {
+            Rebalance::Assign(partitions) => partitions.elements().iter()
+                .map(|partition| format!("{}/{}", partition.topic(), partition.partition()))
+                .collect(),
+            Rebalance::Revoke(_) => Vec::new(),
+            Rebalance::Error(_) => return,
+        };
+        if let Ok(mut state) = assignment_state().write() {
+            state.assignment = Some(assignment);
+        }
     }
 
     fn commit_callback(&self, result: KafkaResult<()>, _offsets: &TopicPartitionList) {
@@ -25,47 +52,271 @@ impl ConsumerContext for CustomContext {
     }
 }
 
+/// Partitions assigned to the consumer and its lag, as `<topic>/<partition>`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AssignmentStatus {
+    /// `None` until the first rebalance
+    pub assignment: Option<Vec<String>>,
+    /// `None` until the first statistics
+    pub lag: Option<HashMap<String, i64>>,
+}
+
+fn assignment_state() -> &'static RwLock<AssignmentStatus> {
+    static ASSIGNMENT_STATUS: OnceLock<RwLock<AssignmentStatus>> = OnceLock::new();
+    ASSIGNMENT_STATUS.get_or_init(|| RwLock::new(AssignmentStatus::default()))
+}
+
+pub fn assignment_status() -> AssignmentStatus {
+    match assignment_state().read() {
+        Ok(state) => state.clone(),
+        Err(error) => error.into_inner().clone(),
+    }
+}
+
+/// Kafka brokers from `KAFKA_HOST`
+pub fn kafka_host() -> String {
+    match env::var("KAFKA_HOST") {
+        Ok(value) => value,
+        Err(_) => {
+            log::info!("Variable `KAFKA_HOST` Not found from enviroment, loaded from local IP");
+            "127.0.0.1:9092".to_owned()
+        }.to_owned(),
+    }
+}
+
+/// Security properties of the client from enviroment, e.g. SASL/SCRAM over TLS
+const SECURITY_VARIABLES: &[(&str, &str)] = &[
+    ("KAFKA_SECURITY_PROTOCOL", "security.protocol"),
+    ("KAFKA_SASL_MECHANISM", "sasl.mechanism"),
+    ("KAFKA_SASL_USERNAME", "sasl.username"),
+    ("KAFKA_SASL_PASSWORD", "sasl.password"),
+    ("KAFKA_SSL_CA_LOCATION", "ssl.ca.location"),
+    ("KAFKA_SSL_CERTIFICATE_LOCATION", "ssl.certificate.location"),
+    ("KAFKA_SSL_KEY_LOCATION", "ssl.key.location"),
+    ("KAFKA_SSL_KEY_PASSWORD", "ssl.key.password"),
+];
+
+/// Prefix of the variables passed to `rdkafka` as property, e.g. `KAFKA_PROPERTY_FETCH_MAX_BYTES` is `fetch.max.bytes`
+const PROPERTY_PREFIX: &str = "KAFKA_PROPERTY_";
+
+/// Default properties of the consumer, they can be overridden with `KAFKA_PROPERTY_<NAME>`
+const CONSUMER_PROPERTIES: &[(&str, &str)] = &[
+    ("enable.partition.eof", "false"),
+    ("session.timeout.ms", "6000"),
+    ("message.max.bytes", "1000000000"),
+    ("message.copy.max.bytes", "1000000000"),
+    ("receive.message.max.bytes", "2147483647"),
+    ("socket.send.buffer.bytes", "100000000"),
+    ("socket.receive.buffer.bytes", "100000000"),
+    ("queued.max.messages.kbytes", "2097151"),
+    ("fetch.message.max.bytes", "1000000000"),
+    ("max.partition.fetch.bytes", "1000000000"),
+    ("fetch.max.bytes", "2147483135"),
+    ("auto.offset.reset", "earliest"),
+    //  Lag reported by the readiness
+    ("statistics.interval.ms", "10000"),
+];
+
+/// Configuration shared by consumers and producers, the `defaults` are set before the security
+/// and the passthrough properties of the enviroment
+pub fn client_config(brokers: &str, defaults: &[(&str, &str)]) -> ClientConfig {
+    client_config_from_variables(brokers, defaults, &env::vars().collect())
+}
+
+fn client_config_from_variables(brokers: &str, defaults: &[(&str, &str)], variables: &HashMap<String, String>) -> ClientConfig {
+    let mut config = ClientConfig::new();
+    config.set("bootstrap.servers", brokers);
+    for (property, value) in defaults {
+        config.set(*property, *value);
+    }
+    for (variable, property) in SECURITY_VARIABLES {
+        if let Some(value) = variables.get(*variable) {
+            if !value.trim().is_empty() {
+                config.set(*property, value.trim());
+            }
+        }
+    }
+    let mut properties: Vec<(String, String)> = variables.iter()
+        .filter_map(|(variable, value)| {
+            variable.strip_prefix(PROPERTY_PREFIX)
+                .filter(|name| !name.is_empty())
+                .map(|name| (name.to_lowercase().replace('_', "."), value.to_owned()))
+        })
+        .collect();
+    properties.sort();
+    for (property, value) in properties {
+        log::info!("Kafka property `{}` loaded from enviroment", property);
+        config.set(property, value);
+    }
+    config
+}
+
 // A type alias with your custom consumer can be created for convenience.
 type LoggingConsumer = StreamConsumer<CustomContext>;
 
-pub fn create_consumer(brokers: &str, group_id: &str, topics: &[&str]) -> Result<LoggingConsumer, Error> {
+/// Subscription of the consumer, reported by the system info
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionStatus {
+    /// `disabled`, `subscribing`, `subscribed` or `failed`
+    pub state: String,
+    pub topics: Vec<String>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+fn subscription_state() -> &'static RwLock<SubscriptionStatus> {
+    static SUBSCRIPTION_STATUS: OnceLock<RwLock<SubscriptionStatus>> = OnceLock::new();
+    SUBSCRIPTION_STATUS.get_or_init(|| RwLock::new(SubscriptionStatus {
+        state: "disabled".to_owned(),
+        topics: Vec::new(),
+        attempts: 0,
+        last_error: None,
+    }))
+}
+
+fn save_subscription_status(_state: &str, _topics: &[&str], _attempts: u32, _last_error: Option<String>) {
+    if let Ok(mut status) = subscription_state().write() {
+        status.state = _state.to_owned();
+        status.topics = _topics.iter().map(|topic| topic.to_string()).collect();
+        status.attempts = _attempts;
+        status.last_error = _last_error;
+    }
+}
+
+pub fn subscription_status() -> SubscriptionStatus {
+    match subscription_state().read() {
+        Ok(status) => status.clone(),
+        Err(error) => error.into_inner().clone(),
+    }
+}
+
+fn u64_env_var(_name: &str, _default: u64) -> u64 {
+    match env::var(_name) {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+            log::error!("Variable `{}` is invalid, as default {}", _name, _default);
+            _default
+        }),
+        Err(_) => {
+            log::info!("Variable `{}` Not found from enviroment, as default {}", _name, _default);
+            _default
+        },
+    }
+}
+
+fn u32_env_var(_name: &str, _default: u32) -> u32 {
+    match env::var(_name) {
+        Ok(value) => value.trim().parse::<u32>().unwrap_or_else(|_| {
+            log::error!("Variable `{}` is invalid, as default {}", _name, _default);
+            _default
+        }),
+        Err(_) => {
+            log::info!("Variable `{}` Not found from enviroment, as default {}", _name, _default);
+            _default
+        },
+    }
+}
+
+/// Double the backoff of a failed attempt without exceed the maximum
+pub fn next_backoff(_backoff: Duration, _maximum_backoff: Duration) -> Duration {
+    _backoff.saturating_mul(2).min(_maximum_backoff)
+}
+
+/// Create the consumer and subscribe to the topics, a failed subscription is retried with exponential backoff
+/// from `KAFKA_SUBSCRIBE_BACKOFF_MS` up to one minute, until `KAFKA_SUBSCRIBE_ATTEMPTS` (`0` without limit).
+/// The retries are stopped when the future is dropped
+pub async fn create_consumer(brokers: &str, group_id: &str, topics: &[&str]) -> Result<LoggingConsumer, DictionaryError> {
 	let context: CustomContext = CustomContext;
 
-	let consumer_value : KafkaResult<LoggingConsumer> = ClientConfig::new()
+	let consumer_value : KafkaResult<LoggingConsumer> = client_config(brokers, CONSUMER_PROPERTIES)
         .set("group.id", group_id)
-        .set("bootstrap.servers", brokers)
-        .set("enable.partition.eof", "false")
-        .set("session.timeout.ms", "6000")
-        .set("enable.auto.commit", "true")
-		.set("message.max.bytes", "1000000000")
-		.set("message.copy.max.bytes", "1000000000")
-		.set("receive.message.max.bytes", "2147483647")
-		.set("socket.send.buffer.bytes", "100000000")
-		.set("socket.receive.buffer.bytes", "100000000")
-		.set("queued.max.messages.kbytes", "2097151")
-		.set("fetch.message.max.bytes", "1000000000")
-		.set("max.partition.fetch.bytes", "1000000000")
-		.set("fetch.max.bytes", "2147483135")
-		.set("auto.offset.reset", "earliest")
+        //  Offsets are committed after the events are written
+        .set("enable.auto.commit", "false")
         .set_log_level(RDKafkaLogLevel::Debug)
         .create_with_context(context);
-	if consumer_value.is_err() {
-		return Err(Error::new(ErrorKind::InvalidData.into(), consumer_value.err().unwrap()))
-	}
-	let consumer = consumer_value.unwrap();
+	let consumer = match consumer_value {
+		Ok(consumer) => consumer,
+		Err(error) => {
+			save_subscription_status("failed", topics, 0, Some(error.to_string()));
+			return Err(DictionaryError::from(error))
+		},
+	};
+	let maximum_attempts = u32_env_var("KAFKA_SUBSCRIBE_ATTEMPTS", 10);
+	let mut backoff = Duration::from_millis(u64_env_var("KAFKA_SUBSCRIBE_BACKOFF_MS", 1000));
+	let maximum_backoff = Duration::from_secs(60);
+	let mut attempts: u32 = 0;
 	loop {
-		match consumer.subscribe(&topics) {
+		attempts += 1;
+		save_subscription_status("subscribing", topics, attempts, None);
+		let error = match consumer.subscribe(topics) {
 			Ok(()) => {
 				log::info!("Subscribed to topics successfully: {:?}", topics.join(" "));
-				break
-			},
-			Err(e) => {
-				log::warn!("Can't subscribe to specified topics '{:?}': {}", topics, e);
+				save_subscription_status("subscribed", topics, attempts, None);
+				return Ok(consumer)
 			},
+			Err(error) => error,
+		};
+		if maximum_attempts > 0 && attempts >= maximum_attempts {
+			log::error!("Can't subscribe to specified topics '{:?}' after {} attempts: {}", topics, attempts, error);
+			save_subscription_status("failed", topics, attempts, Some(error.to_string()));
+			return Err(DictionaryError::from(error))
 		}
-		let waiting_time = Duration::from_secs(5);
-		thread::sleep(waiting_time);
+		log::warn!("Can't subscribe to specified topics '{:?}', retrying in {:?}: {}", topics, backoff, error);
+		save_subscription_status("subscribing", topics, attempts, Some(error.to_string()));
+		tokio::time::sleep(backoff).await;
+		backoff = next_backoff(backoff, maximum_backoff);
 	}
-	Ok(consumer)
-    // consumer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(_variables: &[(&str, &str)]) -> HashMap<String, String> {
+        _variables.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn security_variables_are_mapped_to_properties() {
+        let config = client_config_from_variables("localhost:9092", &[], &variables(&[
+            ("KAFKA_SECURITY_PROTOCOL", " SASL_SSL "),
+            ("KAFKA_SASL_USERNAME", "adempiere"),
+            ("KAFKA_SSL_KEY_PASSWORD", ""),
+        ]));
+        assert_eq!(config.get("bootstrap.servers"), Some("localhost:9092"));
+        assert_eq!(config.get("security.protocol"), Some("SASL_SSL"));
+        assert_eq!(config.get("sasl.username"), Some("adempiere"));
+        assert_eq!(config.get("ssl.key.password"), None);
+    }
+
+    #[test]
+    fn passthrough_properties_override_the_defaults() {
+        let config = client_config_from_variables("localhost:9092", &[("fetch.max.bytes", "1"), ("session.timeout.ms", "6000")], &variables(&[
+            ("KAFKA_PROPERTY_FETCH_MAX_BYTES", "1024"),
+            ("KAFKA_PROPERTY_", "ignored"),
+            ("KAFKA_HOST", "ignored"),
+        ]));
+        assert_eq!(config.get("fetch.max.bytes"), Some("1024"));
+        assert_eq!(config.get("session.timeout.ms"), Some("6000"));
+        assert_eq!(config.get(""), None);
+        assert_eq!(config.get("host"), None);
+    }
+
+    #[test]
+    fn backoff_is_doubled_up_to_the_maximum() {
+        let maximum = Duration::from_secs(60);
+        assert_eq!(next_backoff(Duration::from_secs(1), maximum), Duration::from_secs(2));
+        assert_eq!(next_backoff(Duration::from_secs(40), maximum), maximum);
+        assert_eq!(next_backoff(Duration::MAX, maximum), maximum);
+    }
+
+    #[test]
+    fn out_of_range_attempts_use_the_default() {
+        env::set_var("KAFKA_TEST_ATTEMPTS_VALID", " 7 ");
+        env::set_var("KAFKA_TEST_ATTEMPTS_OVERFLOW", "4294967296");
+        env::set_var("KAFKA_TEST_ATTEMPTS_NEGATIVE", "-1");
+        assert_eq!(u32_env_var("KAFKA_TEST_ATTEMPTS_VALID", 10), 7);
+        assert_eq!(u32_env_var("KAFKA_TEST_ATTEMPTS_OVERFLOW", 10), 10);
+        assert_eq!(u32_env_var("KAFKA_TEST_ATTEMPTS_NEGATIVE", 10), 10);
+        assert_eq!(u32_env_var("KAFKA_TEST_ATTEMPTS_MISSING", 10), 10);
+    }
 }
@@ -1,2 +1,9 @@
+pub mod cache;
+pub mod dead_letter;
+pub mod event;
+pub mod headers;
+pub mod health;
 pub mod kafka;
-pub mod opensearch;
\ No newline at end of file
+pub mod opensearch;
+pub mod reindex;
+pub mod topic;
\ No newline at end of file
@@ -1,10 +1,23 @@
 use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use opensearch::{OpenSearch, IndexParts, DeleteParts, SearchParts, GetParts};
-use opensearch::http::Url;
-use opensearch::http::transport::{SingleNodeConnectionPool, TransportBuilder};
-use opensearch::indices::{IndicesGetParts, IndicesCreateParts, IndicesDeleteParts};
-use serde_json::Value;
+use opensearch::{OpenSearch, BulkParts, IndexParts, DeleteParts, SearchParts, GetParts};
+use opensearch::http::request::JsonBody;
+use opensearch::auth::{ClientCertificate, Credentials};
+use opensearch::cert::{Certificate, CertificateValidation};
+use opensearch::http::{StatusCode, Url};
+use opensearch::params::VersionType;
+use opensearch::http::response::Response;
+use opensearch::http::transport::{Connection, ConnectionPool, SingleNodeConnectionPool, TransportBuilder};
+use opensearch::cluster::ClusterHealthParts;
+use opensearch::indices::{IndicesGetParts, IndicesCreateParts, IndicesDeleteParts, IndicesGetIndexTemplateParts, IndicesPutIndexTemplateParts};
+use serde_json::{json, Value};
+
+use crate::controller::cache::{index_existence_cache, invalidate_index};
+use crate::error::DictionaryError;
 
 pub trait IndexDocument: Sync {
     //  A index definition for mapping
@@ -19,6 +32,31 @@ pub trait IndexDocument: Sync {
     fn find(self: &Self, _search_value: String) -> serde_json::Value;
 }
 
+static OPENSEARCH_CLIENT: OnceLock<OpenSearch> = OnceLock::new();
+
+/// Round robin pool over all nodes listed in `OPENSEARCH_URL`
+#[derive(Debug, Clone)]
+pub struct MultiNodeConnectionPool {
+    connections: Vec<Connection>,
+    next_connection: Arc<AtomicUsize>,
+}
+
+impl MultiNodeConnectionPool {
+    pub fn new(urls: Vec<Url>) -> Self {
+        MultiNodeConnectionPool {
+            connections: urls.into_iter().map(Connection::new).collect(),
+            next_connection: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl ConnectionPool for MultiNodeConnectionPool {
+    fn next(&self) -> Connection {
+        let position = self.next_connection.fetch_add(1, Ordering::Relaxed);
+        self.connections[position % self.connections.len()].clone()
+    }
+}
+
 pub fn create_opensearch_client() -> Result<OpenSearch, String> {
     let opensearch_url =  match env::var("OPENSEARCH_URL") {
         Ok(value) => value.clone(),
@@ -27,17 +65,47 @@ pub fn create_opensearch_client() -> Result<OpenSearch, String> {
             "http://localhost:9200".to_owned()
         }.to_owned(),
     };
-    let url = match Url::parse(&opensearch_url) {
-        Ok(value) => value,
-        Err(error) => {
-            return Err(error.to_string());
+    let opensearch_timeout: u64 = match env::var("OPENSEARCH_TIMEOUT") {
+        Ok(value) => match value.trim().parse::<u64>() {
+            Ok(seconds) => seconds,
+            Err(error) => return Err(format!("Invalid `OPENSEARCH_TIMEOUT` {:?}: {}", value, error)),
+        },
+        Err(_) => {
+            log::info!("Variable `OPENSEARCH_TIMEOUT` Not found from enviroment, as default 30 seconds");
+            30
         },
     };
-    let conn_pool = SingleNodeConnectionPool::new(url);
-    let transport = match TransportBuilder::new(conn_pool)
+    //  Nodes can be separated by comma or whitespace
+    let mut urls: Vec<Url> = Vec::new();
+    for node in opensearch_url.split(|character: char| character == ',' || character.is_whitespace()) {
+        if node.trim().is_empty() {
+            continue;
+        }
+        match Url::parse(node.trim()) {
+            Ok(value) => urls.push(value),
+            Err(error) => {
+                return Err(format!("Invalid OpenSearch node {:?}: {}", node, error));
+            },
+        }
+    }
+    let transport_builder = match urls.len() {
+        0 => return Err("Variable `OPENSEARCH_URL` does not contain any node".to_owned()),
+        1 => TransportBuilder::new(SingleNodeConnectionPool::new(urls.remove(0))),
+        _ => {
+            log::info!("OpenSearch connection pool with {} nodes", urls.len());
+            TransportBuilder::new(MultiNodeConnectionPool::new(urls))
+        },
+    };
+    let mut transport_builder = transport_builder
         .disable_proxy()
-        // .auth(Credentials::Basic("admin".to_owned(), "admin".to_owned()))
-        .build() {
+        .timeout(Duration::from_secs(opensearch_timeout));
+    if let Some(credentials) = opensearch_credentials()? {
+        transport_builder = transport_builder.auth(credentials);
+    }
+    if let Some(certificate_validation) = opensearch_certificate_validation()? {
+        transport_builder = transport_builder.cert_validation(certificate_validation);
+    }
+    let transport = match transport_builder.build() {
             Ok(value) => value,
             Err(error) => {
                 return Err(error.to_string());
@@ -46,14 +114,106 @@ pub fn create_opensearch_client() -> Result<OpenSearch, String> {
     Ok(OpenSearch::new(transport))
 }
 
-pub async fn exists_index(_index_name: String) -> Result<bool, String> {
+fn optional_env_var(_name: &str) -> Option<String> {
+    match env::var(_name) {
+        Ok(value) if !value.trim().is_empty() => Some(value.trim().to_owned()),
+        _ => None,
+    }
+}
+
+fn read_file(_variable: &str, _path: &str) -> Result<Vec<u8>, String> {
+    match fs::read(_path) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(format!("Error reading `{}` file {:?}: {}", _variable, _path, error)),
+    }
+}
+
+/// Only one authentication method can be used: basic, API key or client certificate
+fn opensearch_credentials() -> Result<Option<Credentials>, String> {
+    let mut credentials: Vec<Credentials> = Vec::new();
+    if let Some(username) = optional_env_var("OPENSEARCH_USERNAME") {
+        let password = optional_env_var("OPENSEARCH_PASSWORD").unwrap_or_default();
+        log::info!("OpenSearch authentication with basic credentials of {:?}", username);
+        credentials.push(Credentials::Basic(username, password));
+    }
+    if let Some(api_key) = optional_env_var("OPENSEARCH_API_KEY") {
+        let api_key_id = match optional_env_var("OPENSEARCH_API_KEY_ID") {
+            Some(value) => value,
+            None => return Err("Variable `OPENSEARCH_API_KEY_ID` is mandatory with `OPENSEARCH_API_KEY`".to_owned()),
+        };
+        log::info!("OpenSearch authentication with API key {:?}", api_key_id);
+        credentials.push(Credentials::ApiKey(api_key_id, api_key));
+    }
+    if let Some(certificate_path) = optional_env_var("OPENSEARCH_CLIENT_CERTIFICATE") {
+        let certificate = read_file("OPENSEARCH_CLIENT_CERTIFICATE", &certificate_path)?;
+        let password = optional_env_var("OPENSEARCH_CLIENT_CERTIFICATE_PASSWORD");
+        log::info!("OpenSearch authentication with client certificate {:?}", certificate_path);
+        credentials.push(Credentials::Certificate(ClientCertificate::Pkcs12(certificate, password)));
+    }
+    if credentials.len() > 1 {
+        return Err("Only one OpenSearch authentication method can be configured".to_owned());
+    }
+    Ok(credentials.pop())
+}
+
+fn opensearch_certificate_validation() -> Result<Option<CertificateValidation>, String> {
+    let skip_validation = optional_env_var("OPENSEARCH_SKIP_CERTIFICATE_VALIDATION").unwrap_or("N".to_owned());
+    if skip_validation.eq("Y") {
+        log::warn!("OpenSearch certificate validation is disabled");
+        return Ok(Some(CertificateValidation::None));
+    }
+    if let Some(ca_path) = optional_env_var("OPENSEARCH_CA_CERTIFICATE") {
+        let ca_bundle = read_file("OPENSEARCH_CA_CERTIFICATE", &ca_path)?;
+        let certificate = match Certificate::from_pem(&ca_bundle) {
+            Ok(value) => value,
+            Err(error) => return Err(format!("Invalid CA bundle {:?}: {}", ca_path, error)),
+        };
+        log::info!("OpenSearch certificates validated with CA bundle {:?}", ca_path);
+        return Ok(Some(CertificateValidation::Full(certificate)));
+    }
+    Ok(None)
+}
+
+/// Set the shared client before first use, e.g. to point tests to another cluster
+pub fn set_opensearch_client(client: OpenSearch) -> Result<(), String> {
+    match OPENSEARCH_CLIENT.set(client) {
+        Ok(_) => Ok(()),
+        Err(_) => Err("OpenSearch client is already initialized".to_owned()),
+    }
+}
+
+/// Get the shared client, it is created from enviroment on first call
+pub fn get_opensearch_client() -> Result<&'static OpenSearch, DictionaryError> {
+    if let Some(client) = OPENSEARCH_CLIENT.get() {
+        return Ok(client);
+    }
     let client = match create_opensearch_client() {
-        Ok(client_value) => client_value,
-        Err(error) => {
-            log::error!("{:?}", error);
-            return Err(error.to_string());
-        }
+        Ok(value) => value,
+        Err(error) => return Err(DictionaryError::OpenSearch(error)),
     };
+    Ok(OPENSEARCH_CLIENT.get_or_init(|| client))
+}
+
+/// Read the error type from a failed response, e.g. `index_not_found_exception`
+pub(crate) async fn error_from_response(_response: Response, _message: String) -> DictionaryError {
+    let status_code = _response.status_code();
+    let response_body = _response.json::<Value>().await.unwrap_or(Value::Null);
+    if response_body["error"]["type"].as_str() == Some("index_not_found_exception") {
+        return DictionaryError::IndexNotFound(_message);
+    }
+    if status_code == StatusCode::NOT_FOUND {
+        return DictionaryError::DocumentNotFound(_message);
+    }
+    DictionaryError::OpenSearch(format!("{} ({}): {}", _message, status_code, response_body))
+}
+
+pub async fn exists_index(_index_name: String) -> Result<bool, DictionaryError> {
+    match index_existence_cache().get(&_index_name) {
+        Some(true) => return Ok(true),
+        Some(false) => return Err(DictionaryError::IndexNotFound(format!("Index {:?} Not Found", _index_name))),
+        None => {},
+    }
+    let client = get_opensearch_client()?;
     //  Get data
     let _response = client.indices()
         .get(IndicesGetParts::Index(&[&_index_name]))
@@ -62,184 +222,414 @@ pub async fn exists_index(_index_name: String) -> Result<bool, String> {
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
     if response.status_code().is_success() {
+        index_existence_cache().insert(_index_name, true);
         Ok(true)
+    } else if response.status_code() == StatusCode::NOT_FOUND {
+        index_existence_cache().insert(_index_name.to_owned(), false);
+        Err(DictionaryError::IndexNotFound(format!("Index {:?} Not Found", _index_name)))
     } else {
-        Err(format!("Index {:?} Not Found", _index_name))
+        Err(error_from_response(response, format!("Error getting index {:?}", _index_name)).await)
     }
 }
 
-pub async fn create_index_definition(_index: &dyn IndexDocument) -> Result<bool, String> {
-    let client = match create_opensearch_client() {
-        Ok(client_value) => client_value,
-        Err(error) => {
-            log::error!("{:?}", error);
-            return Err(error.to_string());
-        }
-    };
-    //  Get data
+static INDEX_TEMPLATES_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Mark that the index templates of all resources are registered, new indexes take the mapping from them
+pub fn set_index_templates_registered(_registered: bool) {
+    INDEX_TEMPLATES_REGISTERED.store(_registered, Ordering::SeqCst);
+}
+
+pub fn index_templates_registered() -> bool {
+    INDEX_TEMPLATES_REGISTERED.load(Ordering::SeqCst)
+}
+
+/// Physical index behind an alias, e.g. `window_es_mx_11-1712345678901`, a new one is created on each reindex
+pub fn physical_index_name(_alias: &str) -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    format!("{}-{}", _alias, millis)
+}
+
+/// Settings and mappings are taken from the index template when it is registered
+pub fn new_index_body(_index: &dyn IndexDocument) -> Value {
+    if INDEX_TEMPLATES_REGISTERED.load(Ordering::SeqCst) {
+        json!({})
+    } else {
+        _index.mapping()
+    }
+}
+
+/// Concrete indexes of an index or alias name, empty when it does not exist
+pub async fn concrete_indexes(_index_name: &str) -> Result<Vec<String>, DictionaryError> {
+    let client = get_opensearch_client()?;
     let _response = client.indices()
-        .get(IndicesGetParts::Index(&[&_index.index_name()]))
+        .get(IndicesGetParts::Index(&[_index_name]))
         .send().await;
     let response = match _response {
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
+    if response.status_code() == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
     if !response.status_code().is_success() {
-        // Create an index
-        let _response = client
+        return Err(error_from_response(response, format!("Error getting index {:?}", _index_name)).await);
+    }
+    let response_body = response.json::<Value>().await?;
+    let indexes = match response_body.as_object() {
+        Some(indexes) => indexes.keys().cloned().collect(),
+        None => Vec::new(),
+    };
+    Ok(indexes)
+}
+
+/// Create a physical index with the `index_name` of the document as alias
+pub async fn create_index_definition(_index: &dyn IndexDocument) -> Result<bool, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _alias = _index.index_name();
+    if !concrete_indexes(&_alias).await?.is_empty() {
+        return Ok(true);
+    }
+    let mut _body = new_index_body(_index);
+    _body["aliases"] = json!({
+        _alias.to_owned(): {
+            "is_write_index": true
+        }
+    });
+    let _physical_index = physical_index_name(&_alias);
+    let _response = client
         .indices()
-        .create(IndicesCreateParts::Index(&_index.index_name()))
-        .body(_index.mapping())
+        .create(IndicesCreateParts::Index(&_physical_index))
+        .body(_body)
         .send()
         .await;
-        //  
-        match _response {
-            Ok(value) => {
-                if value.status_code().is_success() {
-                    log::info!("Index created: {:?}", _index.index_name());
-                } else {
-                    return Err(format!("Error creating index {:?}({:?})", _index.index_name(), value.text().await));    
-                }
-            }
-            Err(error) => {
-                log::error!("{:?}", error);
-                return Err(error.to_string());
+    match _response {
+        Ok(value) => {
+            if value.status_code().is_success() {
+                log::info!("Index created: {:?} as {:?}", _physical_index, _alias);
+                invalidate_index(&_alias);
+            } else if value.status_code() == StatusCode::BAD_REQUEST && !concrete_indexes(&_alias).await?.is_empty() {
+                //  Created by other writer at the same time
+                invalidate_index(&_alias);
+            } else {
+                return Err(error_from_response(value, format!("Error creating index {:?}", _alias)).await);
             }
         }
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
     }
     Ok(true)
 }
 
-pub async fn delete_index_definition(_index: &dyn IndexDocument) -> Result<bool, String> {
-    let client = match create_opensearch_client() {
-        Ok(client_value) => client_value,
+/// Delete the physical indexes behind the `index_name` of the document
+pub async fn delete_index_definition(_index: &dyn IndexDocument) -> Result<bool, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _index_name = _index.index_name();
+    let _indexes = concrete_indexes(&_index_name).await?;
+    if _indexes.is_empty() {
+        return Ok(true);
+    }
+    let _indexes_reference: Vec<&str> = _indexes.iter().map(|index| index.as_str()).collect();
+    let _response = client
+        .indices()
+        .delete(IndicesDeleteParts::Index(&_indexes_reference))
+        .send()
+        .await;
+    match _response {
+        Ok(value) => {
+            if value.status_code().is_success() {
+                log::info!("Index deleted: {:?} {:?}", _index_name, _indexes);
+                invalidate_index(&_index_name);
+            } else {
+                return Err(error_from_response(value, format!("Error deleting index {:?}", _index_name)).await);
+            }
+        }
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
-    };
-    //  Get data
-    let _response = client.indices()
-        .get(IndicesGetParts::Index(&[&_index.index_name()]))
-        .send().await;
+    }
+    Ok(true)
+}
+
+/// Create or replace an index template, e.g. `{"index_patterns": ["menu_*"], "template": {...}}`
+pub async fn put_index_template(_name: &str, _template: Value) -> Result<bool, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _response = client
+        .indices()
+        .put_index_template(IndicesPutIndexTemplateParts::Name(_name))
+        .body(_template)
+        .send()
+        .await;
     let response = match _response {
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
-    if response.status_code().is_success() {
-        // Create an index
-        let _response = client
-        .indices()
-        .delete(IndicesDeleteParts::Index(&[&_index.index_name()]))
-        .send()
-        .await;
-        //  
-        match _response {
-            Ok(value) => {
-                if value.status_code().is_success() {
-                    log::info!("Index deleted: {:?}", _index.index_name());
-                } else {
-                    return Err(format!("Error deleting index {:?}({:?})", _index.index_name(), value.status_code()));    
-                }
-            }
-            Err(error) => {
-                log::error!("{:?}", error);
-                return Err(error.to_string());
-            }
-        }
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error registering index template {:?}", _name)).await);
     }
+    log::info!("Index template registered: {:?}", _name);
     Ok(true)
 }
 
-pub async fn create(_document: &dyn IndexDocument) -> Result<bool, std::string::String> {
-    let client: OpenSearch = match create_opensearch_client() {
-        Ok(client_value) => client_value,
+/// Names of the index templates that match the pattern, e.g. `dictionary_*`
+pub async fn index_templates(_pattern: &str) -> Result<Vec<String>, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _response = client.indices()
+        .get_index_template(IndicesGetIndexTemplateParts::Name(&[_pattern]))
+        .send().await;
+    let response = match _response {
+        Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
-    let _response = create_index_definition(_document).await;
-    let _response = match _response {
-        Ok(_) => true,
+    if response.status_code() == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error getting index templates {:?}", _pattern)).await);
+    }
+    let response_body = response.json::<Value>().await?;
+    let templates = match response_body["index_templates"].as_array() {
+        Some(templates) => templates.iter()
+            .filter_map(|template| template["name"].as_str().map(|name| name.to_owned()))
+            .collect(),
+        None => Vec::new(),
+    };
+    Ok(templates)
+}
+
+/// Health of the cluster, with `status` as `green`, `yellow` or `red`
+pub async fn cluster_health() -> Result<Value, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _response = client.cluster()
+        .health(ClusterHealthParts::None)
+        .send().await;
+    let response = match _response {
+        Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            false
+            return Err(error.into());
         }
     };
-    match get_by_id(_document).await {
-        Ok(_) => {
-            match delete(_document).await {
-                Ok(_) => {},
-                Err(error) => log::error!("{:?}", error),
-            };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, "Error getting cluster health".to_owned()).await);
+    }
+    Ok(response.json::<Value>().await?)
+}
+
+/// Index or replace the document with one request, with a `_version` (e.g. the timestamp of the event)
+/// an older version can not replace a newer one and it is ignored returning `false`
+pub async fn create(_document: &dyn IndexDocument, _version: Option<i64>) -> Result<bool, DictionaryError> {
+    let client = get_opensearch_client()?;
+    match exists_index(_document.index_name()).await {
+        Ok(_) => {},
+        Err(DictionaryError::IndexNotFound(_)) => {
+            create_index_definition(_document).await?;
         },
-        Err(_) => {},
+        Err(error) => return Err(error),
     };
-    //  Create
-    let _response = client
-        .index(IndexParts::IndexId(&_document.index_name(), &_document.id()))
-        .body(_document.data())
-        .send().await;
-    let _response = match _response {
+    let _index_name = _document.index_name();
+    let _id = _document.id();
+    let mut _request = client
+        .index(IndexParts::IndexId(&_index_name, &_id))
+        .body(_document.data());
+    if let Some(version) = _version {
+        _request = _request
+            .version(version)
+            .version_type(VersionType::ExternalGte);
+    }
+    let _response = match _request.send().await {
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
+    if _response.status_code() == StatusCode::CONFLICT {
+        log::warn!("Record {:?} on {:?} has a newer version than {:?}, ignored", _document.id(), _document.index_name(), _version);
+        return Ok(false);
+    }
     if !_response.status_code().is_success() {
-        return Err(format!("Error inserting record {:?} {:?} {:?}", _document.index_name(), _document.id(), _response.text().await));
+        return Err(error_from_response(_response, format!("Error inserting record {:?} {:?}", _document.index_name(), _document.id())).await);
     }
     Ok(true)
 }
 
-pub async fn delete(_document: &dyn IndexDocument) -> Result<bool, std::string::String> {
-    let client = match create_opensearch_client() {
-        Ok(client_value) => client_value,
-        Err(error) => {
-            log::error!("{:?}", error);
-            return Err(error.to_string());
-        }
-    };
-    //  Create
-    let _response = client
-        .delete(DeleteParts::IndexId(&_document.index_name(), &_document.id()))
-        .send().await;
-    match _response {
+/// Delete the document, with a `_version` a newer document is not deleted, returns `false` when nothing is deleted
+pub async fn delete(_document: &dyn IndexDocument, _version: Option<i64>) -> Result<bool, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _index_name = _document.index_name();
+    let _id = _document.id();
+    let mut _request = client
+        .delete(DeleteParts::IndexId(&_index_name, &_id));
+    if let Some(version) = _version {
+        _request = _request
+            .version(version)
+            .version_type(VersionType::ExternalGte);
+    }
+    let _response = match _request.send().await {
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
+    if _response.status_code() == StatusCode::CONFLICT {
+        log::warn!("Record {:?} on {:?} has a newer version than {:?}, not deleted", _document.id(), _document.index_name(), _version);
+        return Ok(false);
+    }
+    if _response.status_code() == StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    if !_response.status_code().is_success() {
+        return Err(error_from_response(_response, format!("Error deleting record {:?} {:?}", _document.index_name(), _document.id())).await);
+    }
     Ok(true)
 }
 
-pub async fn find(_document: &dyn IndexDocument, _search_value: String, _from: i64, _size: i64) -> Result<Vec<Value>, std::string::String> {
-	let client: OpenSearch = match create_opensearch_client() {
-        Ok(client_value) => client_value,
+/// Write applied by `bulk` for a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Index,
+    Delete,
+}
+
+pub struct BulkOperation<'a> {
+    pub action: BulkAction,
+    pub document: &'a dyn IndexDocument,
+    /// External version, e.g. the timestamp of the event
+    pub version: Option<i64>,
+}
+
+fn bulk_item_result(_item: &Value) -> Result<bool, DictionaryError> {
+    let result = match _item.as_object().and_then(|item| item.values().next()) {
+        Some(result) => result,
+        None => return Err(DictionaryError::Deserialization(format!("Bulk item without result {:?}", _item))),
+    };
+    let status = result["status"].as_u64().unwrap_or(0);
+    let is_blocked = result["error"]["type"] == "cluster_block_exception";
+    match status {
+        200..=299 => Ok(true),
+        //  Newer version stored or document to delete not found
+        409 | 404 => Ok(false),
+        //  Overloaded or unavailable shards and writes blocked by a reindex can be retried
+        _ if is_blocked || status == 429 || (500..=599).contains(&status) => Err(DictionaryError::OpenSearch(format!("Bulk item {:?} on {:?} failed ({}): {}", result["_id"], result["_index"], status, result["error"]))),
+        _ => Err(DictionaryError::DocumentRejected(format!("Bulk item {:?} on {:?} rejected ({}): {}", result["_id"], result["_index"], status, result["error"]))),
+    }
+}
+
+/// Apply the operations with one `_bulk` request, the result of each operation is returned in the same order,
+/// `false` when it was ignored by a newer version or a missing document
+pub async fn bulk(_operations: &[BulkOperation<'_>]) -> Result<Vec<Result<bool, DictionaryError>>, DictionaryError> {
+    if _operations.is_empty() {
+        return Ok(Vec::new());
+    }
+    let client = get_opensearch_client()?;
+    //  Indexes are created before, a bulk index would create them without alias
+    let mut _index_names: Vec<String> = Vec::new();
+    for operation in _operations.iter().filter(|operation| operation.action == BulkAction::Index) {
+        let _index_name = operation.document.index_name();
+        if _index_names.contains(&_index_name) {
+            continue;
+        }
+        match exists_index(_index_name.to_owned()).await {
+            Ok(_) => {},
+            Err(DictionaryError::IndexNotFound(_)) => {
+                create_index_definition(operation.document).await?;
+            },
+            Err(error) => return Err(error),
+        };
+        _index_names.push(_index_name);
+    }
+    let mut _body: Vec<JsonBody<Value>> = Vec::with_capacity(_operations.len() * 2);
+    for operation in _operations {
+        let mut metadata = json!({
+            "_index": operation.document.index_name(),
+            "_id": operation.document.id()
+        });
+        if let Some(version) = operation.version {
+            metadata["version"] = json!(version);
+            metadata["version_type"] = json!("external_gte");
+        }
+        match operation.action {
+            BulkAction::Index => {
+                _body.push(json!({ "index": metadata }).into());
+                _body.push(operation.document.data().into());
+            },
+            BulkAction::Delete => {
+                _body.push(json!({ "delete": metadata }).into());
+            },
+        }
+    }
+    let _response = client
+        .bulk(BulkParts::None)
+        .body(_body)
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
-    //  Create
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error writing bulk of {} records", _operations.len())).await);
+    }
+    let response_body = response.json::<Value>().await?;
+    let items = match response_body["items"].as_array() {
+        Some(items) if items.len() == _operations.len() => items,
+        _ => return Err(DictionaryError::Deserialization(format!("Bulk response without the {} items", _operations.len()))),
+    };
+    Ok(items.iter().map(bulk_item_result).collect())
+}
+
+/// A page of search hits, the `sort` values of the last hit are used as `search_after` of the next page
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub hits: Vec<Value>,
+    pub total: i64,
+    pub last_sort: Option<Value>,
+}
+
+pub async fn find(_document: &dyn IndexDocument, _search_value: String, _from: i64, _size: i64, _search_after: Option<Value>) -> Result<SearchPage, DictionaryError> {
+	let client = get_opensearch_client()?;
+    //  Stable sort to page with `search_after`, `id` break ties of score
+    let mut _body = _document.find(_search_value);
+    if _body.get("sort").is_none() {
+        _body["sort"] = json!([
+            { "_score": { "order": "desc" } },
+            { "id": { "order": "asc" } }
+        ]);
+    }
+    let mut _from = _from;
+    if let Some(search_after) = _search_after {
+        _body["search_after"] = search_after;
+        _from = 0;
+    }
 	let _response: Result<opensearch::http::response::Response, opensearch::Error> = client
         .search(SearchParts::Index(&[&_document.index_name()]))
         .from(_from)
         .size(_size)
-        .body(_document.find(_search_value))
+        .track_total_hits(true)
+        .body(_body)
         .send()
 		.await
 	;
@@ -247,35 +637,44 @@ pub async fn find(_document: &dyn IndexDocument, _search_value: String, _from: i
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
     if !response.status_code().is_success() {
-        return Err(format!("Error finding record {:?}", response.text().await));
+        return Err(error_from_response(response, format!("Error finding record on {:?}", _document.index_name())).await);
     }
     let response_body = match response.json::<Value>().await {
         Ok(response) => response,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(DictionaryError::Deserialization(error.to_string()));
         },
     };
+    let hits = match response_body["hits"]["hits"].as_array() {
+        Some(value) => value,
+        None => return Err(DictionaryError::Deserialization(format!("Search response without hits {:?}", response_body))),
+    };
+    //  `hits.total` is an object since OpenSearch 1.0 and a number before
+    let total: i64 = response_body["hits"]["total"]["value"].as_i64()
+        .or(response_body["hits"]["total"].as_i64())
+        .unwrap_or(hits.len() as i64);
     let mut list: Vec::<Value> = Vec::new();
-    for hit in response_body["hits"]["hits"].as_array().unwrap() {
+    for hit in hits {
         let value = hit["_source"].to_owned();
         list.push(value)
     }
-    Ok(list)
+    let last_sort = hits.last()
+        .map(|hit| hit["sort"].to_owned())
+        .filter(|sort| sort.is_array());
+    Ok(SearchPage {
+        hits: list,
+        total,
+        last_sort
+    })
 }
 
-pub async fn get_by_id(_document: &dyn IndexDocument) -> Result<Value, std::string::String> {
-    let client = match create_opensearch_client() {
-        Ok(client_value) => client_value,
-        Err(error) => {
-            log::error!("{:?}", error);
-            return Err(error.to_string());
-        }
-    };
+pub async fn get_by_id(_document: &dyn IndexDocument) -> Result<Value, DictionaryError> {
+    let client = get_opensearch_client()?;
     //  Create
     let _response = client
         .get(GetParts::IndexId(&_document.index_name(), &_document.id()))
@@ -284,11 +683,11 @@ pub async fn get_by_id(_document: &dyn IndexDocument) -> Result<Value, std::stri
         Ok(value) => value,
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(error.into());
         }
     };
     if !_response.status_code().is_success() {
-        return Err(format!("Error finding record by ID {:?}", _response.text().await));
+        return Err(error_from_response(_response, format!("Record {:?} Not Found on {:?}", _document.id(), _document.index_name())).await);
     }
     let response_body = match _response.json::<Value>().await {
         Ok(response) => {
@@ -296,8 +695,57 @@ pub async fn get_by_id(_document: &dyn IndexDocument) -> Result<Value, std::stri
         },
         Err(error) => {
             log::error!("{:?}", error);
-            return Err(error.to_string());
+            return Err(DictionaryError::Deserialization(error.to_string()));
         },
     };
     Ok(response_body)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk_item(_status: u64) -> Value {
+        json!({
+            "index": {
+                "_index": "window_es_mx",
+                "_id": "1",
+                "status": _status,
+                "error": if _status >= 300 { json!({ "type": "error" }) } else { Value::Null },
+            }
+        })
+    }
+
+    #[test]
+    fn bulk_item_written() {
+        assert_eq!(bulk_item_result(&bulk_item(200)), Ok(true));
+        assert_eq!(bulk_item_result(&bulk_item(201)), Ok(true));
+    }
+
+    #[test]
+    fn bulk_item_ignored_by_newer_version_or_missing_document() {
+        assert_eq!(bulk_item_result(&bulk_item(409)), Ok(false));
+        assert_eq!(bulk_item_result(&bulk_item(404)), Ok(false));
+    }
+
+    #[test]
+    fn bulk_item_failures_are_transient_or_rejected() {
+        for status in [429, 500, 503] {
+            let error = bulk_item_result(&bulk_item(status)).unwrap_err();
+            assert!(matches!(error, DictionaryError::OpenSearch(_)), "{}", status);
+            assert!(error.is_transient(), "{}", status);
+        }
+        let mut blocked = bulk_item(403);
+        blocked["index"]["error"] = json!({ "type": "cluster_block_exception" });
+        assert!(bulk_item_result(&blocked).unwrap_err().is_transient());
+        let error = bulk_item_result(&bulk_item(400)).unwrap_err();
+        assert!(matches!(error, DictionaryError::DocumentRejected(_)));
+        assert!(!error.is_transient());
+    }
+
+    #[test]
+    fn bulk_item_without_result_is_invalid() {
+        assert!(matches!(bulk_item_result(&json!({})), Err(DictionaryError::Deserialization(_))));
+        assert!(matches!(bulk_item_result(&Value::Null), Err(DictionaryError::Deserialization(_))));
+    }
+}
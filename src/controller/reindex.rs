@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use opensearch::http::headers::HeaderMap;
+use opensearch::http::Method;
+use opensearch::http::request::JsonBody;
+use opensearch::indices::{IndicesCreateParts, IndicesDeleteParts, IndicesPutSettingsParts};
+use opensearch::params::Refresh;
+use opensearch::{BulkParts, ClearScrollParts, ScrollParts, SearchParts};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::controller::cache::invalidate_index;
+use crate::controller::opensearch::{concrete_indexes, error_from_response, get_opensearch_client, new_index_body, physical_index_name, IndexDocument};
+use crate::error::DictionaryError;
+
+/// Documents by page read with scroll or deleted with bulk
+const SCROLL_SIZE: usize = 1000;
+const SCROLL_TIME: &str = "1m";
+
+/// Progress of the reindex of an alias into a new physical index
+#[derive(Debug, Clone, Serialize)]
+pub struct ReindexStatus {
+    pub index_name: String,
+    pub source_indexes: Vec<String>,
+    pub destination_index: String,
+    /// `running`, `completed` or `failed`
+    pub state: String,
+    pub total: i64,
+    pub created: i64,
+    pub updated: i64,
+    pub message: Option<String>,
+}
+
+impl ReindexStatus {
+    fn is_running(&self) -> bool {
+        self.state == "running"
+    }
+}
+
+fn reindex_statuses() -> &'static Mutex<HashMap<String, ReindexStatus>> {
+    static REINDEX_STATUSES: OnceLock<Mutex<HashMap<String, ReindexStatus>>> = OnceLock::new();
+    REINDEX_STATUSES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn save_status(_status: &ReindexStatus) {
+    log::info!("Reindex {:?} {}: {}/{} created, {} updated", _status.index_name, _status.state, _status.created, _status.total, _status.updated);
+    if let Ok(mut statuses) = reindex_statuses().lock() {
+        statuses.insert(_status.index_name.to_owned(), _status.clone());
+    }
+}
+
+/// Last reindex of the index name, running or finished
+pub fn reindex_status(_index_name: &str) -> Option<ReindexStatus> {
+    reindex_statuses().lock().ok()?.get(_index_name).cloned()
+}
+
+/// Copy documents between indexes with `_reindex`, the `_version` of the source is kept as external version,
+/// so a document of the destination is only replaced by a newer version
+async fn copy_documents(_source_indexes: &[String], _destination_index: &str) -> Result<String, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _response = client
+        .reindex()
+        .wait_for_completion(false)
+        .refresh(true)
+        .body(json!({
+            "conflicts": "proceed",
+            "source": {
+                "index": _source_indexes
+            },
+            "dest": {
+                "index": _destination_index,
+                "version_type": "external"
+            }
+        }))
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
+    };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error reindexing {:?} into {:?}", _source_indexes, _destination_index)).await);
+    }
+    let response_body = response.json::<Value>().await?;
+    match response_body["task"].as_str() {
+        Some(task) => Ok(task.to_owned()),
+        None => Err(DictionaryError::Deserialization(format!("Reindex response without task {:?}", response_body))),
+    }
+}
+
+/// Add the `_id` and `_version` of the hits to the versions, returns the number of hits
+fn add_hit_versions(_response_body: &Value, _versions: &mut HashMap<String, i64>) -> usize {
+    let hits = match _response_body["hits"]["hits"].as_array() {
+        Some(hits) => hits,
+        None => return 0,
+    };
+    for hit in hits {
+        if let (Some(id), Some(version)) = (hit["_id"].as_str(), hit["_version"].as_i64()) {
+            _versions.insert(id.to_owned(), version);
+        }
+    }
+    hits.len()
+}
+
+/// Version of each document of the indexes, read with a scroll
+async fn document_versions(_indexes: &[String]) -> Result<HashMap<String, i64>, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _indexes_reference: Vec<&str> = _indexes.iter().map(|index| index.as_str()).collect();
+    let mut versions: HashMap<String, i64> = HashMap::new();
+    let mut _response = client
+        .search(SearchParts::Index(&_indexes_reference))
+        .scroll(SCROLL_TIME)
+        .body(json!({
+            "size": SCROLL_SIZE,
+            "_source": false,
+            "version": true,
+            "sort": ["_doc"]
+        }))
+        .send()
+        .await;
+    let mut scroll_id: Option<String> = None;
+    loop {
+        let response = match _response {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("{:?}", error);
+                return Err(error.into());
+            }
+        };
+        if !response.status_code().is_success() {
+            return Err(error_from_response(response, format!("Error reading documents of {:?}", _indexes)).await);
+        }
+        let response_body = response.json::<Value>().await?;
+        scroll_id = response_body["_scroll_id"].as_str().map(|id| id.to_owned()).or(scroll_id);
+        let _scroll_id = match &scroll_id {
+            Some(id) if add_hit_versions(&response_body, &mut versions) > 0 => id.to_owned(),
+            _ => break,
+        };
+        _response = client
+            .scroll(ScrollParts::None)
+            .body(json!({
+                "scroll": SCROLL_TIME,
+                "scroll_id": _scroll_id
+            }))
+            .send()
+            .await;
+    }
+    if let Some(_scroll_id) = scroll_id {
+        let _scroll_ids = [_scroll_id.as_str()];
+        if let Err(error) = client.clear_scroll(ClearScrollParts::ScrollId(&_scroll_ids)).send().await {
+            log::warn!("Error clearing scroll: {:?}", error);
+        }
+    }
+    Ok(versions)
+}
+
+/// Delete the documents copied to the destination that are deleted on the source, a document written
+/// on the destination after the copy has a greater version and it is not deleted
+async fn delete_removed_documents(_destination_index: &str, _copied_versions: &HashMap<String, i64>, _source_versions: &HashMap<String, i64>) -> Result<usize, DictionaryError> {
+    let client = get_opensearch_client()?;
+    let removed: Vec<(&String, &i64)> = _copied_versions.iter()
+        .filter(|(id, _)| !_source_versions.contains_key(*id))
+        .collect();
+    for chunk in removed.chunks(SCROLL_SIZE) {
+        let _body: Vec<JsonBody<Value>> = chunk.iter()
+            .map(|(id, version)| json!({
+                "delete": {
+                    "_id": id,
+                    "version": version,
+                    "version_type": "external_gte"
+                }
+            }).into())
+            .collect();
+        let _response = client
+            .bulk(BulkParts::Index(_destination_index))
+            .refresh(Refresh::True)
+            .body(_body)
+            .send()
+            .await;
+        let response = match _response {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("{:?}", error);
+                return Err(error.into());
+            }
+        };
+        if !response.status_code().is_success() {
+            return Err(error_from_response(response, format!("Error deleting documents of {:?}", _destination_index)).await);
+        }
+        let response_body = response.json::<Value>().await?;
+        let items = response_body["items"].as_array().cloned().unwrap_or_default();
+        for item in items {
+            let status = item["delete"]["status"].as_u64().unwrap_or(0);
+            //  Not found or replaced after the copy
+            if !(200..300).contains(&status) && status != 404 && status != 409 {
+                return Err(DictionaryError::OpenSearch(format!("Error deleting document of {:?}: {}", _destination_index, item["delete"]["error"])));
+            }
+        }
+    }
+    Ok(removed.len())
+}
+
+/// Poll the reindex task until it is completed, the status is updated on each poll
+async fn wait_for_task(_task: &str, _status: &mut ReindexStatus) -> Result<(), DictionaryError> {
+    let client = get_opensearch_client()?;
+    loop {
+        let _response = client
+            .send(Method::Get, &format!("/_tasks/{}", _task), HeaderMap::new(), None::<&()>, None::<String>, None)
+            .await;
+        let response = match _response {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("{:?}", error);
+                return Err(error.into());
+            }
+        };
+        if !response.status_code().is_success() {
+            return Err(error_from_response(response, format!("Error getting reindex task {:?}", _task)).await);
+        }
+        let response_body = response.json::<Value>().await?;
+        let task_status = &response_body["task"]["status"];
+        _status.total = task_status["total"].as_i64().unwrap_or(_status.total);
+        _status.created = task_status["created"].as_i64().unwrap_or(_status.created);
+        _status.updated = task_status["updated"].as_i64().unwrap_or(_status.updated);
+        save_status(_status);
+        if response_body["completed"].as_bool().unwrap_or(false) {
+            let failures = &response_body["response"]["failures"];
+            if failures.as_array().map(|failures| !failures.is_empty()).unwrap_or(false) {
+                return Err(DictionaryError::OpenSearch(format!("Reindex task {:?} with failures: {}", _task, failures)));
+            }
+            if !response_body["error"].is_null() {
+                return Err(DictionaryError::OpenSearch(format!("Reindex task {:?} failed: {}", _task, response_body["error"])));
+            }
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Move the alias to the new index in one atomic operation, an old index with the same name of the alias is removed
+async fn switch_alias(_index_name: &str, _source_indexes: &[String], _destination_index: &str) -> Result<(), DictionaryError> {
+    let client = get_opensearch_client()?;
+    let mut actions: Vec<Value> = vec![json!({
+        "add": {
+            "index": _destination_index,
+            "alias": _index_name,
+            "is_write_index": true
+        }
+    })];
+    for source_index in _source_indexes {
+        if source_index == _index_name {
+            actions.push(json!({
+                "remove_index": {
+                    "index": source_index
+                }
+            }));
+        } else {
+            actions.push(json!({
+                "remove": {
+                    "index": source_index,
+                    "alias": _index_name
+                }
+            }));
+        }
+    }
+    let _response = client
+        .indices()
+        .update_aliases()
+        .body(json!({
+            "actions": actions
+        }))
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
+    };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error switching alias {:?} to {:?}", _index_name, _destination_index)).await);
+    }
+    Ok(())
+}
+
+/// Block or allow the writes on the indexes, a blocked write fails with `cluster_block_exception` and it is retried
+async fn set_write_block(_indexes: &[String], _is_blocked: bool) -> Result<(), DictionaryError> {
+    let client = get_opensearch_client()?;
+    let _indexes_reference: Vec<&str> = _indexes.iter().map(|index| index.as_str()).collect();
+    let _response = client
+        .indices()
+        .put_settings(IndicesPutSettingsParts::Index(&_indexes_reference))
+        .body(json!({
+            "index.blocks.write": _is_blocked
+        }))
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
+    };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error setting write block of {:?}", _indexes)).await);
+    }
+    Ok(())
+}
+
+/// Copy again the documents written or deleted while the first copy was running, the old indexes do not change
+async fn catch_up(_status: &ReindexStatus, _copied_versions: &HashMap<String, i64>) -> Result<(), DictionaryError> {
+    let source_versions = document_versions(&_status.source_indexes).await?;
+    let mut catch_up_status = _status.clone();
+    let task = copy_documents(&_status.source_indexes, &_status.destination_index).await?;
+    wait_for_task(&task, &mut catch_up_status).await?;
+    let deleted = delete_removed_documents(&_status.destination_index, _copied_versions, &source_versions).await?;
+    log::info!("Reindex {:?}: {} documents deleted while copying", _status.index_name, deleted);
+    Ok(())
+}
+
+async fn delete_indexes(_indexes: &[String]) -> Result<(), DictionaryError> {
+    if _indexes.is_empty() {
+        return Ok(());
+    }
+    let client = get_opensearch_client()?;
+    let _indexes_reference: Vec<&str> = _indexes.iter().map(|index| index.as_str()).collect();
+    let _response = client
+        .indices()
+        .delete(IndicesDeleteParts::Index(&_indexes_reference))
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
+    };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error deleting indexes {:?}", _indexes)).await);
+    }
+    Ok(())
+}
+
+async fn run_reindex(_index: &dyn IndexDocument, _status: &mut ReindexStatus) -> Result<(), DictionaryError> {
+    let client = get_opensearch_client()?;
+    //  New versioned index with the current mapping
+    let _response = client
+        .indices()
+        .create(IndicesCreateParts::Index(&_status.destination_index))
+        .body(new_index_body(_index))
+        .send()
+        .await;
+    let response = match _response {
+        Ok(value) => value,
+        Err(error) => {
+            log::error!("{:?}", error);
+            return Err(error.into());
+        }
+    };
+    if !response.status_code().is_success() {
+        return Err(error_from_response(response, format!("Error creating index {:?}", _status.destination_index)).await);
+    }
+
+    let task = copy_documents(&_status.source_indexes, &_status.destination_index).await?;
+    wait_for_task(&task, _status).await?;
+    let copied_versions = document_versions(std::slice::from_ref(&_status.destination_index)).await?;
+
+    //  Documents written or deleted while the copy was running are copied again when the old indexes do not
+    //  change: after the alias points to the new index, or for an index created before the aliases, that can
+    //  not live with the alias, while its writes are blocked and retried by the consumer
+    let source_indexes = _status.source_indexes.to_owned();
+    let is_alias = !source_indexes.contains(&_status.index_name);
+    if is_alias {
+        switch_alias(&_status.index_name, &source_indexes, &_status.destination_index).await?;
+        invalidate_index(&_status.index_name);
+        catch_up(_status, &copied_versions).await?;
+        return delete_indexes(&source_indexes).await;
+    }
+    set_write_block(&source_indexes, true).await?;
+    let result = match catch_up(_status, &copied_versions).await {
+        Ok(_) => switch_alias(&_status.index_name, &source_indexes, &_status.destination_index).await,
+        Err(error) => Err(error),
+    };
+    match result {
+        Ok(_) => invalidate_index(&_status.index_name),
+        //  The old index keeps receiving the writes
+        Err(_) => if let Err(error) = set_write_block(&source_indexes, false).await {
+            log::error!("Writes of {:?} are still blocked: {}", source_indexes, error);
+        },
+    }
+    result
+}
+
+/// Save the status as running only when the index is not already being reindexed, checked with the same lock
+fn reserve_reindex(_status: &ReindexStatus) -> Result<(), DictionaryError> {
+    let mut statuses = match reindex_statuses().lock() {
+        Ok(statuses) => statuses,
+        Err(error) => return Err(DictionaryError::OpenSearch(format!("Reindex status unavailable: {}", error))),
+    };
+    if statuses.get(&_status.index_name).map(|status| status.is_running()).unwrap_or(false) {
+        return Err(DictionaryError::InvalidParameter(format!("Index {:?} is already being reindexed", _status.index_name)));
+    }
+    statuses.insert(_status.index_name.to_owned(), _status.clone());
+    Ok(())
+}
+
+/// Validate and register a reindex of the `index_name` of the document, it is executed with `execute_reindex`
+pub async fn start_reindex(_index: &dyn IndexDocument) -> Result<ReindexStatus, DictionaryError> {
+    let _index_name = _index.index_name();
+    let mut _status = ReindexStatus {
+        index_name: _index_name.to_owned(),
+        source_indexes: Vec::new(),
+        destination_index: physical_index_name(&_index_name),
+        state: "running".to_owned(),
+        total: 0,
+        created: 0,
+        updated: 0,
+        message: None,
+    };
+    reserve_reindex(&_status)?;
+    let source_indexes = match concrete_indexes(&_index_name).await {
+        Ok(source_indexes) if source_indexes.is_empty() => Err(DictionaryError::IndexNotFound(format!("Index {:?} Not Found", _index_name))),
+        result => result,
+    };
+    match source_indexes {
+        Ok(source_indexes) => {
+            _status.source_indexes = source_indexes;
+            save_status(&_status);
+            Ok(_status)
+        },
+        Err(error) => {
+            _status.state = "failed".to_owned();
+            _status.message = Some(error.to_string());
+            save_status(&_status);
+            Err(error)
+        },
+    }
+}
+
+/// Copy the documents into a new versioned index with the current mapping and switch the alias,
+/// the progress is reported with `reindex_status`
+pub async fn execute_reindex(_index: &dyn IndexDocument, _status: ReindexStatus) -> Result<ReindexStatus, DictionaryError> {
+    let mut _status = _status;
+    match run_reindex(_index, &mut _status).await {
+        Ok(_) => {
+            _status.state = "completed".to_owned();
+            save_status(&_status);
+            Ok(_status)
+        },
+        Err(error) => {
+            _status.state = "failed".to_owned();
+            _status.message = Some(error.to_string());
+            save_status(&_status);
+            Err(error)
+        },
+    }
+}
+
+/// Reindex the `index_name` of the document and wait until it is finished
+pub async fn reindex(_index: &dyn IndexDocument) -> Result<ReindexStatus, DictionaryError> {
+    let _status = start_reindex(_index).await?;
+    execute_reindex(_index, _status).await
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+use serde::Deserialize;
+
+use crate::controller::opensearch::IndexDocument;
+use crate::models::resource::DictionaryResource;
+
+/// Read the document of a message payload, `None` when the message does not have document
+pub type TopicHandler = fn(&str) -> Result<Option<Box<dyn IndexDocument + Send>>, String>;
+
+/// Payload of the resource events, e.g. `{"document": {...}}`
+#[derive(Deserialize)]
+struct EventPayload<T> {
+    document: Option<T>,
+}
+
+/// Handler of a topic, with the index prefix of its resource
+#[derive(Clone, Copy)]
+struct RegisteredTopic {
+    handler: TopicHandler,
+    /// `None` for custom handlers, their messages can not change the target index
+    index_prefix: Option<&'static str>,
+}
+
+fn topic_handlers() -> &'static RwLock<HashMap<String, RegisteredTopic>> {
+    static TOPIC_HANDLERS: OnceLock<RwLock<HashMap<String, RegisteredTopic>>> = OnceLock::new();
+    TOPIC_HANDLERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn unknown_topics() -> &'static Mutex<HashMap<String, u64>> {
+    static UNKNOWN_TOPICS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+    UNKNOWN_TOPICS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Handler of the resource, the payload is deserialized into the model of the resource.
+/// A document without `id` can not be written, it is an error instead of a panic of the consumer
+pub fn resource_document<T: DictionaryResource>(_payload: &str) -> Result<Option<Box<dyn IndexDocument + Send>>, String> {
+    let document = match serde_json::from_str::<EventPayload<T>>(_payload) {
+        Ok(value) => value.document,
+        Err(error) => return Err(error.to_string()),
+    };
+    match document {
+        Some(document) if document.data()["id"].is_null() => Err("document without id".to_owned()),
+        Some(document) => Ok(Some(Box::new(document))),
+        None => Ok(None),
+    }
+}
+
+/// Topics of the resource from `KAFKA_TOPIC_<PREFIX>`, separated by spaces, e.g. `KAFKA_TOPIC_WINDOW=adempiere.dictionary.window`.
+/// The prefix of the resource is used as default
+pub fn resource_topics<T: DictionaryResource>() -> Vec<String> {
+    let variable = format!("KAFKA_TOPIC_{}", T::INDEX_PREFIX.to_uppercase());
+    match env::var(&variable) {
+        Ok(value) if !value.trim().is_empty() => value.split_whitespace().map(|topic| topic.to_owned()).collect(),
+        _ => {
+            log::info!("Variable `{}` Not found from enviroment, as default `{}`", variable, T::INDEX_PREFIX);
+            vec![T::INDEX_PREFIX.to_owned()]
+        },
+    }
+}
+
+fn register_topic(_topic: &str, _registered_topic: RegisteredTopic) {
+    if let Ok(mut handlers) = topic_handlers().write() {
+        if handlers.insert(_topic.to_owned(), _registered_topic).is_some() {
+            log::warn!("Handler of topic {:?} replaced", _topic);
+        }
+    }
+}
+
+/// Register a handler for the topic, a previous handler of the topic is replaced
+pub fn register_topic_handler(_topic: &str, _handler: TopicHandler) {
+    register_topic(_topic, RegisteredTopic {
+        handler: _handler,
+        index_prefix: None,
+    });
+}
+
+/// Register the topics of the resource with the handler of its model
+pub fn register_resource_topics<T: DictionaryResource>() {
+    for topic in resource_topics::<T>() {
+        log::info!("Topic {:?} registered for {}", topic, T::RESOURCE_NAME);
+        register_topic(&topic, RegisteredTopic {
+            handler: resource_document::<T>,
+            index_prefix: Some(T::INDEX_PREFIX),
+        });
+    }
+}
+
+/// Topics with a handler, sorted by name
+pub fn registered_topics() -> Vec<String> {
+    let mut topics: Vec<String> = match topic_handlers().read() {
+        Ok(handlers) => handlers.keys().cloned().collect(),
+        Err(_) => Vec::new(),
+    };
+    topics.sort();
+    topics
+}
+
+fn topic_handler(_topic: &str) -> Option<TopicHandler> {
+    topic_handlers().read().ok()?.get(_topic).map(|registered_topic| registered_topic.handler)
+}
+
+/// Index prefix of the resource of the topic, e.g. `window`
+pub fn topic_index_prefix(_topic: &str) -> Option<&'static str> {
+    topic_handlers().read().ok()?.get(_topic)?.index_prefix
+}
+
+/// Messages received from topics without handler, by topic
+pub fn unknown_topic_messages() -> HashMap<String, u64> {
+    match unknown_topics().lock() {
+        Ok(topics) => topics.clone(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn record_unknown_topic(_topic: &str) {
+    let count = match unknown_topics().lock() {
+        Ok(mut topics) => {
+            let count = topics.entry(_topic.to_owned()).or_insert(0);
+            *count += 1;
+            *count
+        },
+        Err(_) => 0,
+    };
+    log::warn!("Message of topic {:?} without handler skipped, {} messages of this topic", _topic, count);
+}
+
+/// Document of the message with the handler of the topic, messages of unknown topics are skipped and counted
+pub fn topic_document(_topic: &str, _payload: &str) -> Result<Option<Box<dyn IndexDocument + Send>>, String> {
+    match topic_handler(_topic) {
+        Some(handler) => handler(_payload),
+        None => {
+            record_unknown_topic(_topic);
+            Ok(None)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::menu::Menu;
+
+    fn failing_document(_payload: &str) -> Result<Option<Box<dyn IndexDocument + Send>>, String> {
+        Err(format!("custom handler {}", _payload))
+    }
+
+    #[test]
+    fn resource_document_reads_the_model() {
+        let document = resource_document::<Menu>(r#"{"document": {"id": 10, "name": "Sales"}}"#).unwrap().unwrap();
+        assert_eq!(document.data()["id"], 10);
+        assert!(resource_document::<Menu>(r#"{"document": null}"#).unwrap().is_none());
+        assert!(resource_document::<Menu>("{}").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_resource_document_is_an_error() {
+        assert_eq!(resource_document::<Menu>(r#"{"document": {"name": "Sales"}}"#).err(), Some("document without id".to_owned()));
+        assert!(resource_document::<Menu>("not json").is_err());
+    }
+
+    #[test]
+    fn registered_topic_uses_its_handler() {
+        register_topic_handler("test.custom", failing_document);
+        assert_eq!(topic_document("test.custom", "payload").err(), Some("custom handler payload".to_owned()));
+        assert_eq!(topic_index_prefix("test.custom"), None);
+        assert!(registered_topics().contains(&"test.custom".to_owned()));
+    }
+
+    #[test]
+    fn resource_topic_has_index_prefix() {
+        register_topic("test.menu", RegisteredTopic {
+            handler: resource_document::<Menu>,
+            index_prefix: Some(Menu::INDEX_PREFIX),
+        });
+        assert_eq!(topic_index_prefix("test.menu"), Some("menu"));
+        assert!(topic_document("test.menu", r#"{"document": {"id": 1}}"#).unwrap().is_some());
+    }
+
+    #[test]
+    fn unknown_topic_is_skipped_and_counted() {
+        assert!(topic_document("test.unknown", "{}").unwrap().is_none());
+        assert!(topic_document("test.unknown", "{}").unwrap().is_none());
+        assert_eq!(unknown_topic_messages().get("test.unknown"), Some(&2));
+        assert_eq!(topic_index_prefix("test.unknown"), None);
+    }
+}
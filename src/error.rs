@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Errors returned by controller and models, each one with a machine-readable code
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictionaryError {
+    /// A mandatory request parameter is missing or empty
+    MandatoryParameter(String),
+    /// A request parameter has a value that can not be used
+    InvalidParameter(String),
+    /// None of the resolved indexes exists
+    IndexNotFound(String),
+    /// The index exists but does not contain the document
+    DocumentNotFound(String),
+    /// OpenSearch can not be reached or answered with an error, it can be retried
+    OpenSearch(String),
+    /// OpenSearch rejected the document, e.g. it does not match the mapping
+    DocumentRejected(String),
+    /// A stored or received document does not match the model
+    Deserialization(String),
+    /// Kafka can not be reached or answered with an error
+    Kafka(String),
+    /// The request does not have a valid token
+    Unauthorized(String),
+}
+
+impl DictionaryError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DictionaryError::MandatoryParameter(_) => "mandatory_parameter",
+            DictionaryError::InvalidParameter(_) => "invalid_parameter",
+            DictionaryError::IndexNotFound(_) => "index_not_found",
+            DictionaryError::DocumentNotFound(_) => "document_not_found",
+            DictionaryError::OpenSearch(_) => "opensearch_error",
+            DictionaryError::DocumentRejected(_) => "document_rejected",
+            DictionaryError::Deserialization(_) => "deserialization_error",
+            DictionaryError::Kafka(_) => "kafka_error",
+            DictionaryError::Unauthorized(_) => "unauthorized",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            DictionaryError::MandatoryParameter(message)
+            | DictionaryError::InvalidParameter(message)
+            | DictionaryError::IndexNotFound(message)
+            | DictionaryError::DocumentNotFound(message)
+            | DictionaryError::OpenSearch(message)
+            | DictionaryError::DocumentRejected(message)
+            | DictionaryError::Deserialization(message)
+            | DictionaryError::Kafka(message)
+            | DictionaryError::Unauthorized(message) => message,
+        }
+    }
+
+    /// The same request can succeed later, e.g. OpenSearch is unavailable or overloaded
+    pub fn is_transient(&self) -> bool {
+        matches!(self, DictionaryError::OpenSearch(_))
+    }
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+impl From<opensearch::Error> for DictionaryError {
+    fn from(error: opensearch::Error) -> Self {
+        DictionaryError::OpenSearch(error.to_string())
+    }
+}
+
+impl From<rdkafka::error::KafkaError> for DictionaryError {
+    fn from(error: rdkafka::error::KafkaError) -> Self {
+        DictionaryError::Kafka(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DictionaryError {
+    fn from(error: serde_json::Error) -> Self {
+        DictionaryError::Deserialization(error.to_string())
+    }
+}
@@ -1,5 +1,9 @@
+//  Large index mappings are built with `serde_json::json!`
+#![recursion_limit = "256"]
+
 pub mod models;
 pub mod controller;
+pub mod error;
 
 // #[cfg(test)]
 // mod tests {
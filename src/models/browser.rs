@@ -1,11 +1,10 @@
 use serde::{Deserialize, Serialize};
 use salvo::prelude::*;
 use serde_json::json;
-use std::{io::ErrorKind, io::Error};
 
-use crate::controller::opensearch::{IndexDocument, get_by_id, find, exists_index};
+use crate::controller::opensearch::IndexDocument;
 
-use super::{client_index, user_index, role_index};
+use super::{IndexLevel, mapping::{boolean_field, dependent_field, dynamic_mapping, entity_field, integer_field, keyword_field, reference_field, text_field, unindexed_field}, resource::DictionaryResource, search::{search_query, search_settings, search_text_field}};
 
 #[derive(Deserialize, Extractible, Debug, Clone)]
 #[salvo(extract(default_source(from = "body")))]
@@ -18,11 +17,6 @@ pub struct BrowserResponse {
     pub browser: Option<Browser>
 }
 
-#[derive(Serialize, Debug, Clone)]
-pub struct BrowserListResponse {
-    pub browsers: Option<Vec<Browser>>
-}
-
 impl Default for BrowserResponse {
     fn default() -> Self {
         BrowserResponse { 
@@ -181,17 +175,111 @@ impl Browser {
     }
 }
 
+fn table_mapping() -> serde_json::Value {
+    json!({
+        "properties" : {
+            "uuid" : keyword_field(),
+            "id" : integer_field(),
+            "name" : text_field(),
+            "table_name" : keyword_field(),
+            "description" : text_field(),
+            "help" : text_field(),
+            "is_document" : boolean_field(),
+            "is_deleteable" : boolean_field(),
+            "is_view" : boolean_field()
+        }
+    })
+}
+
+fn field_mapping() -> serde_json::Value {
+    json!({
+        "type" : "object",
+        "properties" : {
+            "uuid" : keyword_field(),
+            "id" : integer_field(),
+            "column_name" : keyword_field(),
+            "name" : text_field(),
+            "description" : text_field(),
+            "help" : text_field(),
+            "is_active" : boolean_field(),
+            "display_type" : integer_field(),
+            "callout" : unindexed_field(),
+            "is_order_by" : boolean_field(),
+            "sort_sequence" : integer_field(),
+            "is_key" : boolean_field(),
+            "is_identifier" : boolean_field(),
+            //	Value Properties
+            "is_range" : boolean_field(),
+            "default_value" : unindexed_field(),
+            "default_value_to" : unindexed_field(),
+            "value_format" : unindexed_field(),
+            "min_value" : unindexed_field(),
+            "max_value" : unindexed_field(),
+            //	Display Properties
+            "is_displayed" : boolean_field(),
+            "is_query_criteria" : boolean_field(),
+            "display_logic" : unindexed_field(),
+            "sequence" : integer_field(),
+            "grid_sequence" : integer_field(),
+            "is_displayed_as_panel" : keyword_field(),
+            "is_displayed_as_table" : keyword_field(),
+            //	Editable Properties
+            "is_read_only" : boolean_field(),
+            "read_only_logic" : unindexed_field(),
+            "is_info_only" : boolean_field(),
+            //	Mandatory Properties
+            "is_mandatory" : boolean_field(),
+            //	External Info
+            "element_name" : keyword_field(),
+            "context_column_names" : keyword_field(),
+            "reference" : reference_field(),
+            "dependent_fields" : dependent_field()
+        }
+    })
+}
+
+/// Fields used on search with its boost
+const SEARCH_FIELDS: &[(&str, f32)] = &[("name", 3.0), ("description", 2.0), ("help", 1.0)];
+
 impl IndexDocument for Browser {
     fn mapping(self: &Self) -> serde_json::Value {
         json!({
+            "settings" : search_settings(self.language.as_ref()),
             "mappings" : {
+                "dynamic" : dynamic_mapping(),
                 "properties" : {
                     "uuid" : { "type" : "text" },
                     "id" : { "type" : "integer" },
                     "code" : { "type" : "text" },
-                    "name" : { "type" : "text" },
-                    "description" : { "type" : "text" },
-                    "help" : { "type" : "text" }
+                    "name" : search_text_field(),
+                    "description" : search_text_field(),
+                    "help" : search_text_field(),
+                    "is_active" : boolean_field(),
+                    "is_execute_query_by_default" : boolean_field(),
+                    "is_collapsible_by_default" : boolean_field(),
+                    "is_selected_by_default" : boolean_field(),
+                    "is_show_total" : boolean_field(),
+                    "field_key" : keyword_field(),
+                    // Record Attributes
+                    "access_level" : keyword_field(),
+                    "is_updateable" : boolean_field(),
+                    "is_deleteable" : boolean_field(),
+                    "table_name" : keyword_field(),
+                    "table" : table_mapping(),
+                    //	Index
+                    "index_value" : keyword_field(),
+                    "language" : keyword_field(),
+                    "client_id" : integer_field(),
+                    "role_id" : integer_field(),
+                    "user_id" : integer_field(),
+                    // External Reference
+                    "context_column_names" : keyword_field(),
+                    "process_id" : integer_field(),
+                    "process" : entity_field(),
+                    "window_id" : integer_field(),
+                    "window" : entity_field(),
+                    //	Browse Fields
+                    "fields" : field_mapping()
                 }
             }
         })
@@ -213,17 +301,27 @@ impl IndexDocument for Browser {
     }
 
     fn find(self: &Self, _search_value: String) -> serde_json::Value {
-        let mut query = "*".to_owned();
-        query.push_str(&_search_value.to_owned());
-        query.push_str(&"*".to_owned());
+        search_query(_search_value, SEARCH_FIELDS)
+    }
+}
 
-        json!({
-            "query": {
-                "query_string": {
-                  "query": query
-                }
-            }
-        })
+impl DictionaryResource for Browser {
+    const INDEX_PREFIX: &'static str = "browser";
+    const RESOURCE_NAME: &'static str = "Browser";
+    const LIST_NAME: &'static str = "browsers";
+    const INDEX_FALLBACK: &'static [IndexLevel] = &[IndexLevel::User, IndexLevel::Role, IndexLevel::Client];
+
+    fn with_index(_id: Option<i32>, _index_value: String) -> Self {
+        let mut browser = Browser::from_id(_id);
+        browser.index_value = Some(_index_value);
+        browser
+    }
+
+    fn normalize(&mut self) {
+        // sort fields by sequence
+        if let Some(ref mut fields) = self.fields {
+            fields.sort_by_key(|field| field.sequence.unwrap_or(0));
+        }
     }
 }
 
@@ -267,128 +365,3 @@ pub struct DependendField {
     pub parent_uuid: Option<String>,
     pub parent_name: Option<String>
 }
-
-pub async fn browser_from_id(_id: Option<i32>, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<Browser, String> {
-	if _id.is_none() || _id.map(|id| id <= 0).unwrap_or(false) {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Browser Identifier is Mandatory").to_string());
-	}
-    let mut _document = Browser::from_id(_id);
-
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error: {:?}", error.to_string());
-			return Err(error.to_string())
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-    _document.index_value = Some(_index_name);
-    let _browser_document: &dyn IndexDocument = &_document;
-    match get_by_id(_browser_document).await {
-        Ok(value) => {
-			let mut browser: Browser = serde_json::from_value(value).unwrap();
-            log::info!("Finded Value: {:?}", browser.id);
-
-			// sort fields by sequence
-			if let Some(ref mut fields) = browser.fields {
-				fields.sort_by_key(|field| field.sequence.clone().unwrap_or(0));
-			}
-
-            Ok(
-                browser
-            )
-        },
-        Err(error) => {
-			log::error!("{}", error);
-            Err(error)
-        },
-    }
-}
-
-async fn get_index_name(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<String, std::io::Error> {
-    //  Validate
-    if _language.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Language is Mandatory"));
-    }
-    if _client_id.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Client is Mandatory"));
-    }
-    if _role_id.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Role is Mandatory"));
-    }
-
-	let _index: String = "browser".to_string();
-
-	let _user_index = user_index(_index.to_owned(), _language, _client_id, _role_id, _user_id);
-    let _role_index = role_index(_index.to_owned(), _language, _client_id, _role_id);
-	let _client_index = client_index(_index.to_owned(), _language, _client_id);
-
-    //  Find index
-    match exists_index(_user_index.to_owned()).await {
-		Ok(_) => {
-			log::info!("Find with user index `{:}`", _user_index);
-			Ok(_user_index)
-		},
-        Err(_) => {
-			log::warn!("No user index `{:}`", _user_index);
-            match exists_index(_role_index.to_owned()).await {
-                Ok(_) => {
-					log::info!("Find with role index `{:}`", _role_index);
-					Ok(_role_index)
-				},
-				Err(error) => {
-					log::warn!("No role index `{:}`", _role_index);
-					match exists_index(_client_index.to_owned()).await {
-						Ok(_) => {
-							log::info!("Find with client index `{:}`", _client_index);
-							Ok(_client_index)
-						},
-						Err(_) => {
-							log::error!("No client index `{:}`", _client_index);
-							return Err(Error::new(ErrorKind::InvalidData.into(), error))
-						}
-					}
-                }
-            }
-        }
-    }
-}
-
-pub async fn browsers(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>, _search_value: Option<&String>) -> Result<BrowserListResponse, std::io::Error> {
-    let _search_value = match _search_value {
-        Some(value) => value.clone(),
-        None => "".to_owned()
-    };
-
-	//  Find index
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error: {:?}", error.to_string());
-			return Err(Error::new(ErrorKind::InvalidData.into(), error))
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-    let mut _document = Browser::default();
-    _document.index_value = Some(_index_name);
-    let _browser_document: &dyn IndexDocument = &_document;
-    match find(_browser_document, _search_value, 0, 10).await {
-        Ok(values) => {
-            let mut browsers_list: Vec<Browser> = vec![];
-            for value in values {
-				let mut browser: Browser = serde_json::from_value(value).unwrap();
-				// sort fields by sequence
-				if let Some(ref mut fields) = browser.fields {
-					fields.sort_by_key(|field| field.sequence.clone().unwrap_or(0));
-				}
-                browsers_list.push(browser.to_owned());
-            }
-            Ok(BrowserListResponse {
-                browsers: Some(browsers_list)
-            })
-        },
-        Err(error) => Err(Error::new(ErrorKind::InvalidData.into(), error))
-    }
-}
@@ -0,0 +1,85 @@
+use std::env;
+use std::sync::OnceLock;
+
+use serde_json::{json, Value};
+
+/// Policy for fields without mapping from `INDEX_DYNAMIC_MAPPING`:
+/// `false` keep them only on `_source` (default), `strict` reject the document and `true` map them
+pub fn dynamic_mapping() -> Value {
+	static DYNAMIC_MAPPING: OnceLock<String> = OnceLock::new();
+	let policy = DYNAMIC_MAPPING.get_or_init(|| {
+		match env::var("INDEX_DYNAMIC_MAPPING") {
+			Ok(value) => match value.trim().to_lowercase().as_str() {
+				"true" => "true".to_owned(),
+				"false" => "false".to_owned(),
+				"strict" => "strict".to_owned(),
+				_ => {
+					log::error!("Variable `INDEX_DYNAMIC_MAPPING` is invalid, as default `false`");
+					"false".to_owned()
+				},
+			},
+			Err(_) => {
+				log::info!("Variable `INDEX_DYNAMIC_MAPPING` Not found from enviroment, as default `false`");
+				"false".to_owned()
+			},
+		}
+	});
+	json!(policy)
+}
+
+pub fn keyword_field() -> Value {
+	json!({ "type" : "keyword" })
+}
+
+pub fn text_field() -> Value {
+	json!({ "type" : "text" })
+}
+
+pub fn integer_field() -> Value {
+	json!({ "type" : "integer" })
+}
+
+pub fn boolean_field() -> Value {
+	json!({ "type" : "boolean" })
+}
+
+/// Kept on `_source` without parse or index it, e.g. SQL and logic expressions
+pub fn unindexed_field() -> Value {
+	json!({ "type" : "object", "enabled" : false })
+}
+
+/// Reference to other dictionary record with uuid, id, name, description and help
+pub fn entity_field() -> Value {
+	json!({
+		"properties" : {
+			"uuid" : keyword_field(),
+			"id" : integer_field(),
+			"name" : text_field(),
+			"description" : text_field(),
+			"help" : text_field()
+		}
+	})
+}
+
+/// Context columns used by the reference of a field
+pub fn reference_field() -> Value {
+	json!({
+		"properties" : {
+			"context_column_names" : keyword_field()
+		}
+	})
+}
+
+/// Fields that are refreshed when the value of a field changes
+pub fn dependent_field() -> Value {
+	json!({
+		"properties" : {
+			"uuid" : keyword_field(),
+			"id" : integer_field(),
+			"column_name" : keyword_field(),
+			"parent_id" : integer_field(),
+			"parent_uuid" : keyword_field(),
+			"parent_name" : text_field()
+		}
+	})
+}
@@ -1,9 +1,8 @@
 use serde::{Deserialize, Serialize};
 use salvo::prelude::*;
 use serde_json::json;
-use std::{io::ErrorKind, io::Error};
 
-use crate::{controller::opensearch::{IndexDocument, get_by_id, find, exists_index}, models::{user_index, role_index}};
+use crate::{controller::opensearch::IndexDocument, models::{IndexLevel, resource::DictionaryResource, search::{search_query, search_settings, search_text_field}}};
 
 #[derive(Deserialize, Extractible, Debug, Clone)]
 #[salvo(extract(default_source(from = "body")))]
@@ -16,11 +15,6 @@ pub struct MenuResponse {
     pub menu: Option<Menu>
 }
 
-#[derive(Serialize, Debug, Clone)]
-pub struct MenuListResponse {
-    pub menus: Option<Vec<Menu>>
-}
-
 impl Default for MenuResponse {
     fn default() -> Self {
         MenuResponse { 
@@ -100,17 +94,21 @@ impl Menu {
     }
 }
 
+/// Fields used on search with its boost
+const SEARCH_FIELDS: &[(&str, f32)] = &[("name", 3.0), ("description", 1.0)];
+
 impl IndexDocument for Menu {
     fn mapping(self: &Self) -> serde_json::Value {
         json!({
+            "settings" : search_settings(self.language.as_ref()),
             "mappings" : {
                 "properties" : {
                     "uuid" : { "type" : "text" },
                     "id" : { "type" : "integer" },
                     "parent_id" : { "type" : "integer" },
                     "sequence" : { "type" : "integer" },
-                    "name" : { "type" : "text" },
-                    "description" : { "type" : "text" }
+                    "name" : search_text_field(),
+                    "description" : search_text_field()
                 }
             }
         })
@@ -132,17 +130,34 @@ impl IndexDocument for Menu {
     }
 
     fn find(self: &Self, _search_value: String) -> serde_json::Value {
-        let mut query = "*".to_owned();
-        query.push_str(&_search_value.to_owned());
-        query.push_str(&"*".to_owned());
+        // root menu nodes by sequence on all pages, `id` break ties to page with `search_after`
+        let mut query = search_query(_search_value, SEARCH_FIELDS);
+        query["sort"] = json!([
+            { "sequence": { "order": "asc", "missing": 0 } },
+            { "id": { "order": "asc" } }
+        ]);
+        query
+    }
+}
 
-        json!({
-            "query": {
-                "query_string": {
-                  "query": query
-                }
-            }
-        })
+impl DictionaryResource for Menu {
+    const INDEX_PREFIX: &'static str = "menu";
+    const RESOURCE_NAME: &'static str = "Menu";
+    const LIST_NAME: &'static str = "menus";
+    const INDEX_FALLBACK: &'static [IndexLevel] = &[IndexLevel::User, IndexLevel::Role];
+    const DEFAULT_PAGE_SIZE: i64 = 100;
+
+    fn with_index(_id: Option<i32>, _index_value: String) -> Self {
+        let mut menu = Menu::from_id(_id);
+        menu.index_value = Some(_index_value);
+        menu
+    }
+
+    fn normalize(&mut self) {
+        // sort menu children nodes by sequence
+        if let Some(ref mut children) = self.children {
+            children.sort_by_key(|child| child.sequence.unwrap_or(0));
+        }
     }
 }
 
@@ -190,138 +205,3 @@ pub struct Workflow {
     pub description: Option<String>,
     pub help: Option<String>,
 }
-
-pub async fn menu_from_id(_id: Option<i32>, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<Menu, String> {
-	if _id.is_none() || _id.map(|id| id <= 0).unwrap_or(false) {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Menu Identifier is Mandatory").to_string());
-	}
-    let mut _document = Menu::from_id(_id);
-
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error: {:?}", error.to_string());
-			return Err(error.to_string())
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-	_document.index_value = Some(_index_name);
-    let _menu_document: &dyn IndexDocument = &_document;
-    match get_by_id(_menu_document).await {
-        Ok(value) => {
-			let mut menu: Menu = serde_json::from_value(value).unwrap();
-            log::info!("Finded Value: {:?}", menu.id);
-
-			// sort menu children nodes by sequence
-			if let Some(ref mut children) = menu.children {
-				children.sort_by_key(|child| child.sequence.clone().unwrap_or(0));
-			}
-
-            Ok(
-                menu
-            )
-        },
-        Err(error) => {
-			log::error!("{}", error);
-            Err(error)
-        },
-    }
-}
-
-async fn get_index_name(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<String, std::io::Error> {
-	//  Validate
-	if _language.is_none() {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Language is Mandatory"));
-	}
-	if _client_id.is_none() {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Client is Mandatory"));
-	}
-	if _role_id.is_none() {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Role is Mandatory"));
-	}
-
-	let _index: String = "menu".to_string();
-
-	let _user_index = user_index(_index.to_owned(), _language, _client_id, _role_id, _user_id);
-    let _role_index = role_index(_index.to_owned(), _language, _client_id, _role_id);
-
-	//  Find index
-	match exists_index(_user_index.to_owned()).await {
-		Ok(_) => {
-			log::info!("Find with user index `{:}`", _user_index);
-			Ok(_user_index)
-		},
-		Err(_) => {
-			log::warn!("No user index `{:}`", _user_index);
-			match exists_index(_role_index.to_owned()).await {
-				Ok(_) => {
-					log::info!("Find with role index `{:}`", _role_index);
-					Ok(_role_index)
-				},
-				Err(error) => {
-					log::error!("No role index `{:}`", _role_index);
-					return Err(Error::new(ErrorKind::InvalidData.into(), error))
-				}
-            }
-		}
-	}
-}
-
-pub async fn menus(
-	_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>,
-	_search_value: Option<&String>, _page_number: Option<&String>, _page_size: Option<&String>
-) -> Result<MenuListResponse, std::io::Error> {
-	let _search_value = match _search_value {
-		Some(value) => value.clone(),
-		None => "".to_owned()
-	};
-
-	//  Find index
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error: {:?}", error.to_string());
-			return Err(Error::new(ErrorKind::InvalidData.into(), error))
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-    let mut _document = Menu::default();
-    _document.index_value = Some(_index_name);
-    let _menu_document: &dyn IndexDocument = &_document;
-
-	// pagination
-	let page_number: i64 = match _page_number {
-		Some(value) => value.clone().parse::<i64>().to_owned(),
-		None => "0".parse::<i64>().to_owned()
-	}.unwrap();
-	let page_size: i64 = match _page_size {
-		Some(value) => value.clone().parse::<i64>().to_owned(),
-		None => "100".parse::<i64>().to_owned()
-	}.unwrap();
-
-    match find(_menu_document, _search_value, page_number, page_size).await {
-        Ok(values) => {
-            let mut menus_list: Vec<Menu> = vec![];
-            for value in values {
-				let mut menu: Menu = serde_json::from_value(value).unwrap();
-				// sort menu children nodes by sequence
-				if let Some(ref mut children) = menu.children {
-					children.sort_by_key(|child| child.sequence.clone().unwrap_or(0));
-				}
-                menus_list.push(menu.to_owned());
-            }
-
-			// sort root menu nodes by sequence
-			menus_list.sort_by_key(|menu| menu.sequence.clone().unwrap_or(0));
-
-            Ok(MenuListResponse {
-                menus: Some(menus_list)
-            })
-        },
-		Err(error) => {
-			Err(Error::new(ErrorKind::InvalidData.into(), error))
-		}
-    }
-}
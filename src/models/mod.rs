@@ -4,10 +4,19 @@ pub mod menu;
 pub mod process;
 pub mod window;
 pub mod generic;
+pub mod mapping;
+pub mod resource;
+pub mod search;
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{OnceLock, RwLock};
 
 use serde::{Deserialize, Serialize};
 use salvo::prelude::*;
 
+use crate::controller::cache::invalidate_index_resolutions;
+
 #[derive(Deserialize, Serialize, Extractible, Debug, Clone)]
 pub struct Metadata {
     pub index_value: Option<String>,
@@ -69,3 +78,219 @@ fn user_index(_index_name: String, _language: Option<&String>, _client_id: Optio
 	}
 	_index_to_find.to_lowercase()
 }
+
+/// Level of the index hierarchy `<prefix>_<language>_<client>_<role>_<user>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IndexLevel {
+	Base,
+	Language,
+	Client,
+	Role,
+	User,
+}
+
+impl IndexLevel {
+	pub fn name(&self) -> &'static str {
+		match self {
+			IndexLevel::Base => "base",
+			IndexLevel::Language => "language",
+			IndexLevel::Client => "client",
+			IndexLevel::Role => "role",
+			IndexLevel::User => "user",
+		}
+	}
+
+	pub fn from_name(_name: &str) -> Option<IndexLevel> {
+		match _name.trim().to_lowercase().as_str() {
+			"base" => Some(IndexLevel::Base),
+			"language" => Some(IndexLevel::Language),
+			"client" => Some(IndexLevel::Client),
+			"role" => Some(IndexLevel::Role),
+			"user" => Some(IndexLevel::User),
+			_ => None,
+		}
+	}
+
+	/// Index of the level, `None` when a parameter of the level is missing or invalid, so the level is
+	/// skipped instead of resolving the index of a broader level
+	pub fn index_name(&self, _index_name: String, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Option<String> {
+		let is_valid_id = |id: Option<&String>| id.and_then(|id| id.parse::<i32>().ok()).map(|id| id >= 0).unwrap_or(false);
+		let has_language = _language.map(|language| !language.trim().is_empty()).unwrap_or(false);
+		let has_parameters = match self {
+			IndexLevel::Base => true,
+			IndexLevel::Language => has_language,
+			IndexLevel::Client => has_language && is_valid_id(_client_id),
+			IndexLevel::Role => has_language && is_valid_id(_client_id) && is_valid_id(_role_id),
+			IndexLevel::User => has_language && is_valid_id(_client_id) && is_valid_id(_role_id) && is_valid_id(_user_id),
+		};
+		if !has_parameters {
+			return None;
+		}
+		Some(match self {
+			IndexLevel::Base => default_index(_index_name),
+			IndexLevel::Language => language_index(_index_name, _language),
+			IndexLevel::Client => client_index(_index_name, _language, _client_id),
+			IndexLevel::Role => role_index(_index_name, _language, _client_id, _role_id),
+			IndexLevel::User => user_index(_index_name, _language, _client_id, _role_id, _user_id),
+		})
+	}
+}
+
+/// Ordered levels tried to resolve the index of a resource, the first existing index is used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexPolicy {
+	pub levels: Vec<IndexLevel>,
+}
+
+impl IndexPolicy {
+	pub fn new(_levels: &[IndexLevel]) -> Self {
+		IndexPolicy {
+			levels: _levels.to_vec()
+		}
+	}
+
+	/// Read `<PREFIX>_INDEX_FALLBACK` (e.g. `WINDOW_INDEX_FALLBACK="user role client language"`),
+	/// `INDEX_FALLBACK_TO_LANGUAGE` and `INDEX_FALLBACK_TO_BASE` are appended to all policies
+	pub fn from_env(_index_prefix: &str, _default_levels: &[IndexLevel]) -> Self {
+		let variable_name = format!("{}_INDEX_FALLBACK", _index_prefix.to_uppercase());
+		let mut policy = match env::var(&variable_name) {
+			Ok(value) => match IndexPolicy::parse(&value) {
+				Ok(policy) => policy,
+				Err(error) => {
+					log::error!("Variable `{}` is invalid, loaded with `default` value: {}", variable_name, error);
+					IndexPolicy::new(_default_levels)
+				}
+			},
+			Err(_) => IndexPolicy::new(_default_levels),
+		};
+		let fallback_to_language: String = env::var("INDEX_FALLBACK_TO_LANGUAGE").unwrap_or("N".to_owned());
+		if fallback_to_language.trim().eq("Y") && !policy.levels.contains(&IndexLevel::Language) {
+			policy.levels.push(IndexLevel::Language);
+		}
+		let fallback_to_base: String = env::var("INDEX_FALLBACK_TO_BASE").unwrap_or("N".to_owned());
+		if fallback_to_base.trim().eq("Y") && !policy.levels.contains(&IndexLevel::Base) {
+			policy.levels.push(IndexLevel::Base);
+		}
+		policy
+	}
+
+	pub fn parse(_value: &str) -> Result<Self, String> {
+		let mut levels: Vec<IndexLevel> = Vec::new();
+		for name in _value.split(|character: char| character == ',' || character.is_whitespace()) {
+			if name.trim().is_empty() {
+				continue;
+			}
+			match IndexLevel::from_name(name) {
+				Some(level) => {
+					if !levels.contains(&level) {
+						levels.push(level);
+					}
+				},
+				None => return Err(format!("Unknown index level {:?}", name)),
+			}
+		}
+		if levels.is_empty() {
+			return Err("Without index levels".to_owned());
+		}
+		Ok(IndexPolicy {
+			levels
+		})
+	}
+
+	/// Most specific level, its parameters are mandatory except the user
+	pub fn most_specific_level(&self) -> IndexLevel {
+		self.levels.iter().max().copied().unwrap_or(IndexLevel::Base)
+	}
+
+	/// Indexes to try in order with the level of each one, levels without their parameters are skipped
+	pub fn candidate_indexes(&self, _index_prefix: &str, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Vec<(IndexLevel, String)> {
+		let mut candidates: Vec<(IndexLevel, String)> = Vec::new();
+		for level in &self.levels {
+			match level.index_name(_index_prefix.to_owned(), _language, _client_id, _role_id, _user_id) {
+				Some(index_name) => if !candidates.iter().any(|(_, candidate)| candidate == &index_name) {
+					candidates.push((*level, index_name));
+				},
+				None => log::info!("Index level {} skipped without its parameters", level.name()),
+			}
+		}
+		candidates
+	}
+}
+
+fn index_policies() -> &'static RwLock<HashMap<&'static str, IndexPolicy>> {
+	static INDEX_POLICIES: OnceLock<RwLock<HashMap<&'static str, IndexPolicy>>> = OnceLock::new();
+	INDEX_POLICIES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Policy of a index prefix, it is loaded from enviroment on first use
+pub fn get_index_policy(_index_prefix: &'static str, _default_levels: &[IndexLevel]) -> IndexPolicy {
+	if let Ok(policies) = index_policies().read() {
+		if let Some(policy) = policies.get(_index_prefix) {
+			return policy.clone();
+		}
+	}
+	let policy = IndexPolicy::from_env(_index_prefix, _default_levels);
+	log::info!("Index policy for `{}`: {:?}", _index_prefix, policy.levels);
+	if let Ok(mut policies) = index_policies().write() {
+		policies.entry(_index_prefix).or_insert(policy.clone());
+	}
+	policy
+}
+
+/// Replace the policy of a index prefix, e.g. from tests or an admin endpoint
+pub fn set_index_policy(_index_prefix: &'static str, _policy: IndexPolicy) {
+	if let Ok(mut policies) = index_policies().write() {
+		policies.insert(_index_prefix, _policy);
+	}
+	invalidate_index_resolutions();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parameter(_value: &str) -> Option<String> {
+		Some(_value.to_owned())
+	}
+
+	#[test]
+	fn policy_is_parsed() {
+		assert_eq!(IndexPolicy::parse("user role, client\tlanguage").unwrap().levels, vec![IndexLevel::User, IndexLevel::Role, IndexLevel::Client, IndexLevel::Language]);
+		assert_eq!(IndexPolicy::parse(" Role,,ROLE base ").unwrap().levels, vec![IndexLevel::Role, IndexLevel::Base]);
+		assert!(IndexPolicy::parse("user group").is_err());
+		assert!(IndexPolicy::parse(" , ").is_err());
+	}
+
+	#[test]
+	fn most_specific_level_of_policy() {
+		assert_eq!(IndexPolicy::new(&[IndexLevel::Client, IndexLevel::User, IndexLevel::Language]).most_specific_level(), IndexLevel::User);
+		assert_eq!(IndexPolicy::new(&[]).most_specific_level(), IndexLevel::Base);
+	}
+
+	#[test]
+	fn candidate_indexes_from_most_specific() {
+		let policy = IndexPolicy::new(&[IndexLevel::User, IndexLevel::Role, IndexLevel::Client, IndexLevel::Language, IndexLevel::Base]);
+		let candidates = policy.candidate_indexes("menu", parameter("es_MX").as_ref(), parameter("11").as_ref(), parameter("102").as_ref(), parameter("100").as_ref());
+		assert_eq!(candidates, vec![
+			(IndexLevel::User, "menu_es_mx_11_102_100".to_owned()),
+			(IndexLevel::Role, "menu_es_mx_11_102".to_owned()),
+			(IndexLevel::Client, "menu_es_mx_11".to_owned()),
+			(IndexLevel::Language, "menu_es_mx".to_owned()),
+			(IndexLevel::Base, "menu".to_owned()),
+		]);
+	}
+
+	#[test]
+	fn level_without_parameters_is_skipped() {
+		let policy = IndexPolicy::new(&[IndexLevel::User, IndexLevel::Role]);
+		let language = parameter("es_MX");
+		let client_id = parameter("11");
+		let role_id = parameter("102");
+		for user_id in [None, parameter("user"), parameter("-1")] {
+			let candidates = policy.candidate_indexes("menu", language.as_ref(), client_id.as_ref(), role_id.as_ref(), user_id.as_ref());
+			assert_eq!(candidates, vec![(IndexLevel::Role, "menu_es_mx_11_102".to_owned())], "{:?}", user_id);
+		}
+		let candidates = policy.candidate_indexes("menu", language.as_ref(), client_id.as_ref(), parameter("role").as_ref(), None);
+		assert!(candidates.is_empty());
+	}
+}
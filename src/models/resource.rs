@@ -0,0 +1,378 @@
+use std::env;
+use std::sync::OnceLock;
+
+use serde::{de::DeserializeOwned, ser::SerializeMap, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::{controller::{cache::{index_cache_ttl, index_generation, insert_response, response_cache, response_generation, TtlCache}, opensearch::{IndexDocument, get_by_id, find, exists_index, put_index_template}}, error::DictionaryError};
+
+use super::{get_index_policy, search::{folding_search_settings, search_settings, ANALYZED_LANGUAGES}, IndexLevel, IndexPolicy};
+
+/// A dictionary type stored on indexes `<prefix>_<language>_<client>_<role>_<user>`
+pub trait DictionaryResource: IndexDocument + Serialize + DeserializeOwned + Send + 'static {
+	/// Prefix of the index, e.g. `window` for `window_es_mx_11_102`
+	const INDEX_PREFIX: &'static str;
+	/// Name used on messages, e.g. `Window`
+	const RESOURCE_NAME: &'static str;
+	/// Key of the records on list response, e.g. `windows`
+	const LIST_NAME: &'static str;
+	/// Default levels used to find the index, from the most specific to the least specific,
+	/// it can be overwritten with `<PREFIX>_INDEX_FALLBACK`
+	const INDEX_FALLBACK: &'static [IndexLevel];
+	/// Records by page when the request does not define it
+	const DEFAULT_PAGE_SIZE: i64 = 10;
+
+	/// Resolution policy of the resource
+	fn index_policy() -> IndexPolicy {
+		get_index_policy(Self::INDEX_PREFIX, Self::INDEX_FALLBACK)
+	}
+
+	/// Document used to get or find on a resolved index
+	fn with_index(_id: Option<i32>, _index_value: String) -> Self;
+
+	/// Called for each document read from OpenSearch, e.g. sort children by sequence
+	fn normalize(&mut self) {}
+
+	/// Called for the documents of a list after normalize each one
+	fn normalize_list(_list: &mut [Self]) {}
+}
+
+/// Index found by the resolution policy and the level that matched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedIndex {
+	pub index_name: String,
+	pub level: IndexLevel,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourceResponse<T: DictionaryResource> {
+	pub resource: T,
+	pub index: ResolvedIndex,
+}
+
+/// List response, the records are rendered with the resource `LIST_NAME` as key next to the paging values
+#[derive(Debug, Clone)]
+pub struct ResourceListResponse<T: DictionaryResource> {
+	pub records: Vec<T>,
+	pub index: ResolvedIndex,
+	/// Records that match the search on the index
+	pub total: i64,
+	pub page: i64,
+	pub page_size: i64,
+	/// Send it as `page_token` to get the next page, `None` on the last page
+	pub next_page_token: Option<String>,
+}
+
+impl<T: DictionaryResource> Serialize for ResourceListResponse<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(5))?;
+		map.serialize_entry(T::LIST_NAME, &self.records)?;
+		map.serialize_entry("total", &self.total)?;
+		map.serialize_entry("page", &self.page)?;
+		map.serialize_entry("page_size", &self.page_size)?;
+		map.serialize_entry("next_page_token", &self.next_page_token)?;
+		map.end()
+	}
+}
+
+/// Opaque token with the `sort` values of the last hit of a page, as hexadecimal JSON
+fn encode_page_token(_sort: &Value) -> String {
+	_sort.to_string()
+		.bytes()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
+
+fn decode_page_token(_token: &str) -> Result<Value, DictionaryError> {
+	let invalid_token = || DictionaryError::InvalidParameter(format!("Page Token {:?} is invalid", _token));
+	if _token.len() % 2 == 1 || !_token.is_ascii() {
+		return Err(invalid_token());
+	}
+	let bytes: Vec<u8> = (0.._token.len())
+		.step_by(2)
+		.map(|position| u8::from_str_radix(&_token[position..position + 2], 16))
+		.collect::<Result<Vec<u8>, _>>()
+		.map_err(|_| invalid_token())?;
+	match serde_json::from_slice::<Value>(&bytes) {
+		Ok(value) if value.is_array() => Ok(value),
+		_ => Err(invalid_token()),
+	}
+}
+
+pub async fn resource_from_id<T: DictionaryResource>(_id: Option<i32>, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<ResourceResponse<T>, DictionaryError> {
+	if _id.is_none() || _id.map(|id| id <= 0).unwrap_or(false) {
+		return Err(DictionaryError::MandatoryParameter(format!("{} Identifier is Mandatory", T::RESOURCE_NAME)));
+	}
+
+	let _index = match resolve_index::<T>(_language, _client_id, _role_id, _user_id).await {
+		Ok(index) => index,
+		Err(error) => {
+			log::error!("Index name error to {:?}: {:?}", _id, error.to_string());
+			return Err(error)
+		}
+	};
+	log::info!("Index to search {:}", _index.index_name);
+
+	let _document = T::with_index(_id, _index.index_name.to_owned());
+	let _cache_key = (_index.index_name.to_owned(), _document.id());
+	if let Some(cached) = response_cache().get(&_cache_key) {
+		match serde_json::from_str::<T>(&cached) {
+			Ok(resource) => {
+				log::info!("Cached Value: {:?}", _id);
+				return Ok(ResourceResponse {
+					resource,
+					index: _index
+				})
+			},
+			Err(error) => {
+				log::warn!("Cached value of {:?} is invalid: {}", _cache_key, error);
+				response_cache().remove(&_cache_key);
+			},
+		}
+	}
+	//  A write of the document while it is read changes the generation, then the read value is not cached
+	let _generation = response_generation(&_cache_key);
+	match get_by_id(&_document).await {
+		Ok(value) => {
+			let mut resource: T = serde_json::from_value(value)?;
+			log::info!("Finded Value: {:?}", _id);
+			resource.normalize();
+			if response_cache().is_enabled() {
+				if let Ok(serialized) = serde_json::to_string(&resource) {
+					insert_response(_cache_key, serialized, _generation);
+				}
+			}
+			Ok(ResourceResponse {
+				resource,
+				index: _index
+			})
+		},
+		Err(error) => {
+			log::error!("{}", error);
+			Err(error)
+		},
+	}
+}
+
+/// Default `max_result_window` of OpenSearch, deeper pages are only reached with `page_token`
+const MAXIMUM_RESULT_WINDOW: i64 = 10000;
+
+/// Maximum records by page from `MAXIMUM_PAGE_SIZE`, as default 500
+fn maximum_page_size() -> i64 {
+	static MAXIMUM_PAGE_SIZE: OnceLock<i64> = OnceLock::new();
+	*MAXIMUM_PAGE_SIZE.get_or_init(|| {
+		match env::var("MAXIMUM_PAGE_SIZE") {
+			Ok(value) => match value.trim().parse::<i64>() {
+				Ok(size) if size > 0 => size.min(MAXIMUM_RESULT_WINDOW),
+				_ => {
+					log::error!("Variable `MAXIMUM_PAGE_SIZE` is invalid, as default 500");
+					500
+				},
+			},
+			Err(_) => {
+				log::info!("Variable `MAXIMUM_PAGE_SIZE` Not found from enviroment, as default 500");
+				500
+			},
+		}
+	})
+}
+
+fn parse_page_parameter(_name: &str, _value: Option<&String>, _default: i64) -> Result<i64, DictionaryError> {
+	match _value.map(|value| value.trim()) {
+		None | Some("") => Ok(_default),
+		Some(value) => value.parse::<i64>()
+			.map_err(|_| DictionaryError::InvalidParameter(format!("{} {:?} is not a number", _name, value))),
+	}
+}
+
+/// Search value and paging parameters of a list request
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchParameters<'a> {
+	pub search_value: Option<&'a String>,
+	/// Page starting at `0`, the offset is `page_number * page_size`
+	pub page_number: Option<&'a String>,
+	pub page_size: Option<&'a String>,
+	/// `next_page_token` of the previous page, continue after its last record
+	pub page_token: Option<&'a String>,
+}
+
+pub async fn resources<T: DictionaryResource>(
+	_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>,
+	_parameters: SearchParameters<'_>
+) -> Result<ResourceListResponse<T>, DictionaryError> {
+	let _search_value = match _parameters.search_value {
+		Some(value) => value.clone(),
+		None => "".to_owned()
+	};
+
+	//  Find index
+	let _index = match resolve_index::<T>(_language, _client_id, _role_id, _user_id).await {
+		Ok(index) => index,
+		Err(error) => {
+			log::error!("Index name error: {:?}", error.to_string());
+			return Err(error)
+		}
+	};
+	log::info!("Index to search {:}", _index.index_name);
+
+	let _document = T::with_index(None, _index.index_name.to_owned());
+
+	// pagination
+	let page_number: i64 = parse_page_parameter("Page Number", _parameters.page_number, 0)?;
+	if page_number < 0 {
+		return Err(DictionaryError::InvalidParameter(format!("Page Number {} must be 0 or greater", page_number)));
+	}
+	let page_size: i64 = parse_page_parameter("Page Size", _parameters.page_size, T::DEFAULT_PAGE_SIZE.min(maximum_page_size()))?;
+	if page_size < 1 || page_size > maximum_page_size() {
+		return Err(DictionaryError::InvalidParameter(format!("Page Size {} must be between 1 and {}", page_size, maximum_page_size())));
+	}
+	let offset: i64 = page_number.saturating_mul(page_size);
+	let search_after: Option<Value> = match _parameters.page_token {
+		Some(token) if !token.trim().is_empty() => Some(decode_page_token(token.trim())?),
+		_ => None,
+	};
+	let is_search_after = search_after.is_some();
+	if !is_search_after && offset.saturating_add(page_size) > MAXIMUM_RESULT_WINDOW {
+		return Err(DictionaryError::InvalidParameter(format!("Page Number {} is over the first {} records, use `page_token` to continue", page_number, MAXIMUM_RESULT_WINDOW)));
+	}
+
+	let page = find(&_document, _search_value, offset, page_size, search_after).await?;
+	let mut records: Vec<T> = Vec::with_capacity(page.hits.len());
+	for value in page.hits {
+		let mut resource: T = serde_json::from_value(value)?;
+		resource.normalize();
+		records.push(resource);
+	}
+	T::normalize_list(&mut records);
+
+	//  A full page can have more records, with `search_after` the offset is unknown
+	let is_full_page = records.len() as i64 == page_size && page_size > 0;
+	let has_more = is_full_page && (is_search_after || offset + page_size < page.total);
+	let next_page_token = match page.last_sort {
+		Some(sort) if has_more => Some(encode_page_token(&sort)),
+		_ => None,
+	};
+
+	Ok(ResourceListResponse {
+		records,
+		index: _index,
+		total: page.total,
+		page: page_number,
+		page_size,
+		next_page_token
+	})
+}
+
+/// Resource prefix, language, client, role and user of a request
+type IndexResolutionKey = (&'static str, Option<String>, Option<String>, Option<String>, Option<String>);
+
+fn index_resolution_cache() -> &'static TtlCache<IndexResolutionKey, (ResolvedIndex, u64)> {
+	static INDEX_RESOLUTION_CACHE: OnceLock<TtlCache<IndexResolutionKey, (ResolvedIndex, u64)>> = OnceLock::new();
+	INDEX_RESOLUTION_CACHE.get_or_init(|| TtlCache::new(index_cache_ttl()))
+}
+
+/// Find the first existing index following the resource policy
+pub async fn resolve_index<T: DictionaryResource>(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<ResolvedIndex, DictionaryError> {
+	let _cache_key: IndexResolutionKey = (T::INDEX_PREFIX, _language.cloned(), _client_id.cloned(), _role_id.cloned(), _user_id.cloned());
+	let _generation = index_generation();
+	if let Some((index, generation)) = index_resolution_cache().get(&_cache_key) {
+		if generation == _generation {
+			return Ok(index)
+		}
+	}
+	let _index = find_index::<T>(_language, _client_id, _role_id, _user_id).await?;
+	index_resolution_cache().insert(_cache_key, (_index.clone(), _generation));
+	Ok(_index)
+}
+
+async fn find_index<T: DictionaryResource>(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<ResolvedIndex, DictionaryError> {
+	let policy = T::index_policy();
+	//  Validate, user is always optional
+	let most_specific_level = policy.most_specific_level();
+	if _language.is_none() && most_specific_level >= IndexLevel::Language {
+		return Err(DictionaryError::MandatoryParameter("Language is Mandatory".to_owned()));
+	}
+	if _client_id.is_none() && most_specific_level >= IndexLevel::Client {
+		return Err(DictionaryError::MandatoryParameter("Client is Mandatory".to_owned()));
+	}
+	if _role_id.is_none() && most_specific_level >= IndexLevel::Role {
+		return Err(DictionaryError::MandatoryParameter("Role is Mandatory".to_owned()));
+	}
+
+	//  Find index
+	let mut _tried_indexes: Vec<String> = Vec::new();
+	for (level, _index_to_find) in policy.candidate_indexes(T::INDEX_PREFIX, _language, _client_id, _role_id, _user_id) {
+		match exists_index(_index_to_find.to_owned()).await {
+			Ok(_) => {
+				log::info!("Find with {} index `{:}`", level.name(), _index_to_find);
+				return Ok(ResolvedIndex {
+					index_name: _index_to_find,
+					level
+				})
+			},
+			Err(DictionaryError::IndexNotFound(_)) => {
+				log::warn!("No {} index `{:}`", level.name(), _index_to_find);
+			},
+			Err(error) => return Err(error),
+		}
+		_tried_indexes.push(_index_to_find);
+	}
+	Err(DictionaryError::IndexNotFound(format!("{} Index Not Found, tried {:?}", T::RESOURCE_NAME, _tried_indexes)))
+}
+
+/// Names of the index templates of the resource, the base template and one by analyzed language
+pub fn index_template_names<T: DictionaryResource>() -> Vec<String> {
+	let mut names = vec![format!("dictionary_{}", T::INDEX_PREFIX)];
+	for language in ANALYZED_LANGUAGES {
+		names.push(format!("dictionary_{}_{}", T::INDEX_PREFIX, language));
+	}
+	names
+}
+
+/// Register the index templates of a resource: `<prefix>*` with the accent folding analyzers and
+/// `<prefix>_<language>_*` for each analyzed language, indexes take the mapping from them when created
+pub async fn register_index_templates<T: DictionaryResource>() -> Result<(), DictionaryError> {
+	let mappings = T::with_index(None, T::INDEX_PREFIX.to_owned()).mapping()["mappings"].to_owned();
+	let base_template = json!({
+		"index_patterns": [T::INDEX_PREFIX, format!("{}-*", T::INDEX_PREFIX), format!("{}_*", T::INDEX_PREFIX)],
+		"priority": 100,
+		"template": {
+			"settings": folding_search_settings(),
+			"mappings": mappings
+		}
+	});
+	put_index_template(&format!("dictionary_{}", T::INDEX_PREFIX), base_template).await?;
+	for language in ANALYZED_LANGUAGES {
+		//  A language with country is more specific than the language
+		let priority = if language.contains('_') { 300 } else { 200 };
+		let language_template = json!({
+			"index_patterns": [format!("{}_{}_*", T::INDEX_PREFIX, language), format!("{}_{}-*", T::INDEX_PREFIX, language)],
+			"priority": priority,
+			"template": {
+				"settings": search_settings(Some(&language.to_string())),
+				"mappings": mappings
+			}
+		});
+		put_index_template(&format!("dictionary_{}_{}", T::INDEX_PREFIX, language), language_template).await?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn page_token_is_decoded() {
+		let sort = json!([1.5, "window", 10]);
+		assert_eq!(decode_page_token(&encode_page_token(&sort)), Ok(sort));
+	}
+
+	#[test]
+	fn invalid_page_token_is_rejected() {
+		let not_array = encode_page_token(&json!({"id": 1}));
+		for token in ["5", "zz", "5b31", "é1", not_array.as_str()] {
+			assert!(matches!(decode_page_token(token), Err(DictionaryError::InvalidParameter(_))), "{}", token);
+		}
+	}
+}
@@ -0,0 +1,241 @@
+use serde_json::{json, Value};
+
+/// Maximum of characters of a search value, longer values are truncated
+const MAXIMUM_SEARCH_LENGTH: usize = 256;
+
+/// Languages with its own analyzers, as used on index names (`pt_br` before `pt`)
+pub const ANALYZED_LANGUAGES: &[&str] = &["en", "es", "pt_br", "pt", "fr", "it", "de", "nl"];
+
+/// Stemmer and stop words of a dictionary language, e.g. `es_MX` or `pt_BR`
+fn language_analysis(_language: Option<&String>) -> Option<(&'static str, &'static str)> {
+	let language = match _language {
+		Some(value) => value.trim().to_lowercase().replace('-', "_"),
+		//  Dictionaries without translation are in english
+		None => return Some(("english", "_english_")),
+	};
+	let mut parts = language.split('_');
+	let code = parts.next().unwrap_or("");
+	let country = parts.next().unwrap_or("");
+	match code {
+		"en" => Some(("english", "_english_")),
+		"es" => Some(("light_spanish", "_spanish_")),
+		"pt" if country == "br" => Some(("brazilian", "_brazilian_")),
+		"pt" => Some(("light_portuguese", "_portuguese_")),
+		"fr" => Some(("light_french", "_french_")),
+		"it" => Some(("light_italian", "_italian_")),
+		"de" => Some(("light_german", "_german_")),
+		"nl" => Some(("dutch", "_dutch_")),
+		_ => None,
+	}
+}
+
+/// Analysis settings of a dictionary index for the language of its documents,
+/// `dictionary_analyzer` stems and removes stop words and `prefix` sub fields are indexed with edge n-grams
+pub fn search_settings(_language: Option<&String>) -> Value {
+	analysis_settings(language_analysis(_language))
+}
+
+/// Analysis settings of the languages without stemmer, `dictionary_analyzer` only folds accents
+pub fn folding_search_settings() -> Value {
+	analysis_settings(None)
+}
+
+fn analysis_settings(_language_analysis: Option<(&str, &str)>) -> Value {
+	let mut filters = json!({
+		"prefix_filter": {
+			"type": "edge_ngram",
+			"min_gram": 1,
+			"max_gram": 20
+		}
+	});
+	//  Accents are folded after stemming, the stemmers expect the original words
+	let dictionary_filters: Vec<&str> = match _language_analysis {
+		Some((stemmer, stop_words)) => {
+			filters["dictionary_stop"] = json!({
+				"type": "stop",
+				"stopwords": stop_words
+			});
+			filters["dictionary_stemmer"] = json!({
+				"type": "stemmer",
+				"language": stemmer
+			});
+			vec!["lowercase", "dictionary_stop", "dictionary_stemmer", "asciifolding"]
+		},
+		None => vec!["lowercase", "asciifolding"],
+	};
+	json!({
+		"analysis": {
+			"filter": filters,
+			"analyzer": {
+				"dictionary_analyzer": {
+					"type": "custom",
+					"tokenizer": "standard",
+					"filter": dictionary_filters
+				},
+				"prefix_analyzer": {
+					"type": "custom",
+					"tokenizer": "standard",
+					"filter": ["lowercase", "asciifolding", "prefix_filter"]
+				},
+				"prefix_search_analyzer": {
+					"type": "custom",
+					"tokenizer": "standard",
+					"filter": ["lowercase", "asciifolding"]
+				}
+			}
+		}
+	})
+}
+
+/// Mapping of a searchable text analyzed by the language of the index, with a `prefix` sub field to match while the user types
+pub fn search_text_field() -> Value {
+	json!({
+		"type": "text",
+		"analyzer": "dictionary_analyzer",
+		"fields": {
+			"prefix": {
+				"type": "text",
+				"analyzer": "prefix_analyzer",
+				"search_analyzer": "prefix_search_analyzer"
+			}
+		}
+	})
+}
+
+/// Remove control characters, repeated spaces and the excess of length from the user input
+pub fn normalize_search_value(_search_value: &str) -> String {
+	_search_value
+		.chars()
+		.map(|character| if character.is_control() { ' ' } else { character })
+		.collect::<String>()
+		.split_whitespace()
+		.collect::<Vec<&str>>()
+		.join(" ")
+		.chars()
+		.take(MAXIMUM_SEARCH_LENGTH)
+		.collect()
+}
+
+/// Full text query over the fields with its boost, e.g. `[("name", 3.0), ("help", 1.0)]`.
+/// The value is sent as `multi_match` text, so Lucene syntax (`:`, `(`, `*`, ...) is not interpreted
+pub fn search_query(_search_value: String, _fields: &[(&str, f32)]) -> Value {
+	let search_value = normalize_search_value(&_search_value);
+	if search_value.is_empty() {
+		return json!({
+			"query": {
+				"match_all": {}
+			}
+		})
+	}
+	let fields: Vec<String> = _fields.iter()
+		.map(|(field, boost)| format!("{}^{}", field, boost))
+		.collect();
+	let prefix_fields: Vec<String> = _fields.iter()
+		.map(|(field, boost)| format!("{}.prefix^{}", field, boost))
+		.collect();
+	json!({
+		"query": {
+			"bool": {
+				"should": [
+					{
+						"multi_match": {
+							"query": search_value,
+							"fields": fields,
+							"type": "best_fields",
+							"operator": "and",
+							"boost": 2
+						}
+					},
+					{
+						"multi_match": {
+							"query": search_value,
+							"fields": fields,
+							"type": "best_fields",
+							"operator": "and",
+							"fuzziness": "AUTO",
+							"prefix_length": 1
+						}
+					},
+					{
+						"multi_match": {
+							"query": search_value,
+							"fields": prefix_fields,
+							"type": "best_fields",
+							"operator": "and"
+						}
+					}
+				],
+				"minimum_should_match": 1
+			}
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn language(_value: &str) -> Option<String> {
+		Some(_value.to_owned())
+	}
+
+	#[test]
+	fn language_analysis_by_language_code() {
+		assert_eq!(language_analysis(language("es_MX").as_ref()), Some(("light_spanish", "_spanish_")));
+		assert_eq!(language_analysis(language(" en-US ").as_ref()), Some(("english", "_english_")));
+		assert_eq!(language_analysis(language("pt_BR").as_ref()), Some(("brazilian", "_brazilian_")));
+		assert_eq!(language_analysis(language("pt_PT").as_ref()), Some(("light_portuguese", "_portuguese_")));
+		assert_eq!(language_analysis(language("nl").as_ref()), Some(("dutch", "_dutch_")));
+		assert_eq!(language_analysis(None), Some(("english", "_english_")));
+	}
+
+	#[test]
+	fn other_languages_are_not_stemmed() {
+		for value in ["ja_JP", "ru", "", "esp"] {
+			assert_eq!(language_analysis(language(value).as_ref()), None, "{}", value);
+		}
+	}
+
+	#[test]
+	fn search_settings_of_language() {
+		let settings = search_settings(language("es_MX").as_ref());
+		assert_eq!(settings["analysis"]["filter"]["dictionary_stemmer"]["language"], "light_spanish");
+		assert_eq!(settings["analysis"]["filter"]["dictionary_stop"]["stopwords"], "_spanish_");
+		assert_eq!(settings["analysis"]["analyzer"]["dictionary_analyzer"]["filter"], json!(["lowercase", "dictionary_stop", "dictionary_stemmer", "asciifolding"]));
+		let settings = search_settings(language("ja_JP").as_ref());
+		assert!(settings["analysis"]["filter"]["dictionary_stemmer"].is_null());
+		assert_eq!(settings["analysis"]["analyzer"]["dictionary_analyzer"]["filter"], json!(["lowercase", "asciifolding"]));
+		assert_eq!(folding_search_settings(), settings);
+	}
+
+	#[test]
+	fn search_value_is_normalized() {
+		assert_eq!(normalize_search_value("  sales\torder\n\u{0}line  "), "sales order line");
+		assert_eq!(normalize_search_value(" \t "), "");
+		assert_eq!(normalize_search_value(&"á".repeat(MAXIMUM_SEARCH_LENGTH + 10)).chars().count(), MAXIMUM_SEARCH_LENGTH);
+	}
+
+	#[test]
+	fn empty_search_matches_all() {
+		assert_eq!(search_query(" \n ".to_owned(), &[("name", 3.0)]), json!({"query": {"match_all": {}}}));
+	}
+
+	#[test]
+	fn search_query_boosts_fields_with_fuzzy_and_prefix_matches() {
+		let query = search_query("sales  order".to_owned(), &[("name", 3.0), ("help", 1.0)]);
+		let should = query["query"]["bool"]["should"].as_array().unwrap();
+		assert_eq!(should.len(), 3);
+		assert_eq!(should[0]["multi_match"]["query"], "sales order");
+		assert_eq!(should[0]["multi_match"]["fields"], json!(["name^3", "help^1"]));
+		assert_eq!(should[1]["multi_match"]["fuzziness"], "AUTO");
+		assert_eq!(should[2]["multi_match"]["fields"], json!(["name.prefix^3", "help.prefix^1"]));
+		assert_eq!(query["query"]["bool"]["minimum_should_match"], 1);
+	}
+
+	#[test]
+	fn search_value_is_not_a_query_string() {
+		let query = search_query("name:(a* OR b)".to_owned(), &[("name", 1.0)]);
+		assert_eq!(query["query"]["bool"]["should"][0]["multi_match"]["query"], "name:(a* OR b)");
+		assert!(!query.to_string().contains("query_string"));
+	}
+}
@@ -1,11 +1,10 @@
 use serde::{Deserialize, Serialize};
 use salvo::prelude::*;
 use serde_json::json;
-use std::{io::ErrorKind, io::Error};
 
-use crate::controller::opensearch::{IndexDocument, get_by_id, find, exists_index};
+use crate::controller::opensearch::IndexDocument;
 
-use super::{client_index, user_index, role_index};
+use super::{IndexLevel, mapping::{boolean_field, dependent_field, dynamic_mapping, entity_field, integer_field, keyword_field, reference_field, text_field, unindexed_field}, resource::DictionaryResource, search::{search_query, search_settings, search_text_field}};
 
 #[derive(Deserialize, Extractible, Debug, Clone)]
 #[salvo(extract(default_source(from = "body")))]
@@ -18,11 +17,6 @@ pub struct WindowResponse {
     pub window: Option<Window>
 }
 
-#[derive(Serialize, Debug, Clone)]
-pub struct WindowListResponse {
-    pub windows: Option<Vec<Window>>
-}
-
 impl Default for WindowResponse {
     fn default() -> Self {
         WindowResponse { 
@@ -194,16 +188,168 @@ impl Window {
     }
 }
 
+fn table_mapping() -> serde_json::Value {
+    json!({
+        "properties" : {
+            "table_name" : keyword_field(),
+            "access_level" : keyword_field(),
+            "key_columns" : keyword_field(),
+            "is_view" : boolean_field(),
+            "is_document" : boolean_field(),
+            "is_deleteable" : boolean_field(),
+            "is_change_log" : boolean_field(),
+            "identifier_columns" : keyword_field(),
+            "selection_colums" : keyword_field()
+        }
+    })
+}
+
+fn process_mapping() -> serde_json::Value {
+    json!({
+        "properties" : {
+            "uuid" : keyword_field(),
+            "id" : integer_field(),
+            "name" : text_field(),
+            "description" : text_field(),
+            "help" : text_field(),
+            "is_report" : boolean_field(),
+            "browser_id" : integer_field(),
+            "browser" : entity_field(),
+            "form_id" : integer_field(),
+            "form" : entity_field(),
+            "workflow_id" : integer_field(),
+            "workflow" : entity_field()
+        }
+    })
+}
+
+fn field_mapping() -> serde_json::Value {
+    json!({
+        "type" : "object",
+        "properties" : {
+            "uuid" : keyword_field(),
+            "id" : integer_field(),
+            "name" : text_field(),
+            "description" : text_field(),
+            "help" : text_field(),
+            "is_active" : boolean_field(),
+            "display_type" : integer_field(),
+            "is_allow_copy" : boolean_field(),
+            "is_heading" : boolean_field(),
+            "is_field_only" : boolean_field(),
+            //	Column Properties
+            "column_name" : keyword_field(),
+            "column_sql" : unindexed_field(),
+            "is_key" : boolean_field(),
+            "is_parent" : boolean_field(),
+            "is_translated" : boolean_field(),
+            "is_identifier" : boolean_field(),
+            "identifier_sequence" : integer_field(),
+            "is_selection_column" : boolean_field(),
+            "callout" : unindexed_field(),
+            //	Value Properties
+            "default_value" : unindexed_field(),
+            "field_length" : integer_field(),
+            "value_format" : unindexed_field(),
+            "format_pattern" : unindexed_field(),
+            "value_min" : unindexed_field(),
+            "value_max" : unindexed_field(),
+            "is_encrypted" : boolean_field(),
+            //	Display Properties
+            "is_displayed" : boolean_field(),
+            "display_logic" : unindexed_field(),
+            "sequence" : integer_field(),
+            "is_displayed_grid" : boolean_field(),
+            "grid_sequence" : integer_field(),
+            "is_displayed_as_panel" : keyword_field(),
+            "is_displayed_as_table" : keyword_field(),
+            //	Editable Properties
+            "is_read_only" : boolean_field(),
+            "read_only_logic" : unindexed_field(),
+            "is_updateable" : boolean_field(),
+            "is_always_updateable" : boolean_field(),
+            //	Mandatory Properties
+            "is_mandatory" : boolean_field(),
+            "mandatory_logic" : unindexed_field(),
+            //	External Info
+            "context_column_names" : keyword_field(),
+            "reference" : reference_field(),
+            "dependent_fields" : dependent_field(),
+            "process_id" : integer_field(),
+            "process" : process_mapping()
+        }
+    })
+}
+
+fn tab_mapping() -> serde_json::Value {
+    json!({
+        "type" : "object",
+        "properties" : {
+            "uuid" : keyword_field(),
+            "id" : integer_field(),
+            "name" : text_field(),
+            "description" : text_field(),
+            "help" : text_field(),
+            "is_active" : boolean_field(),
+            // Record attributes
+            "is_insert_record" : boolean_field(),
+            "commit_warning" : unindexed_field(),
+            // Attributes
+            "display_logic" : unindexed_field(),
+            "sequence" : integer_field(),
+            "tab_level" : integer_field(),
+            "is_read_only" : boolean_field(),
+            "read_only_logic" : unindexed_field(),
+            "is_single_row" : boolean_field(),
+            "is_advanced_tab" : boolean_field(),
+            "is_has_tree" : boolean_field(),
+            "is_info_tab" : boolean_field(),
+            "is_translation_tab" : boolean_field(),
+            // Table attributes
+            "table_name" : keyword_field(),
+            "table" : table_mapping(),
+            // Link attributes
+            "parent_column_name" : keyword_field(),
+            "link_column_name" : keyword_field(),
+            // Sort attributes
+            "is_sort_tab" : boolean_field(),
+            "sort_order_column_name" : keyword_field(),
+            "sort_yes_no_column_name" : keyword_field(),
+            // External info
+            "context_column_names" : keyword_field(),
+            "window_id" : integer_field(),
+            "process_id" : integer_field(),
+            "process" : process_mapping(),
+            "processes" : process_mapping(),
+            "fields" : field_mapping()
+        }
+    })
+}
+
+/// Fields used on search with its boost
+const SEARCH_FIELDS: &[(&str, f32)] = &[("name", 3.0), ("description", 2.0), ("help", 1.0)];
+
 impl IndexDocument for Window {
     fn mapping(self: &Self) -> serde_json::Value {
         json!({
+            "settings" : search_settings(self.language.as_ref()),
             "mappings" : {
+                "dynamic" : dynamic_mapping(),
                 "properties" : {
                     "uuid" : { "type" : "text" },
                     "id" : { "type" : "integer" },
-                    "name" : { "type" : "text" },
-                    "description" : { "type" : "text" },
-                    "help" : { "type" : "text" }
+                    "name" : search_text_field(),
+                    "description" : search_text_field(),
+                    "help" : search_text_field(),
+                    "is_active" : boolean_field(),
+                    "window_type" : keyword_field(),
+                    "is_sales_transaction" : boolean_field(),
+                    "index_value" : keyword_field(),
+                    "language" : keyword_field(),
+                    "client_id" : integer_field(),
+                    "role_id" : integer_field(),
+                    "user_id" : integer_field(),
+                    "tabs" : tab_mapping()
                 }
             }
         })
@@ -225,17 +371,33 @@ impl IndexDocument for Window {
     }
 
     fn find(self: &Self, _search_value: String) -> serde_json::Value {
-        let mut query = "*".to_owned();
-        query.push_str(&_search_value.to_owned());
-        query.push_str(&"*".to_owned());
+        search_query(_search_value, SEARCH_FIELDS)
+    }
+}
 
-        json!({
-            "query": {
-                "query_string": {
-                  "query": query
+impl DictionaryResource for Window {
+    const INDEX_PREFIX: &'static str = "window";
+    const RESOURCE_NAME: &'static str = "Window";
+    const LIST_NAME: &'static str = "windows";
+    const INDEX_FALLBACK: &'static [IndexLevel] = &[IndexLevel::User, IndexLevel::Role, IndexLevel::Client];
+
+    fn with_index(_id: Option<i32>, _index_value: String) -> Self {
+        let mut window = Window::from_id(_id);
+        window.index_value = Some(_index_value);
+        window
+    }
+
+    fn normalize(&mut self) {
+        // sort tabs by sequence
+        if let Some(ref mut tabs) = self.tabs {
+            tabs.sort_by_key(|tab| tab.sequence.unwrap_or(0));
+            for tab in tabs.iter_mut() {
+                // sort fields by sequence
+                if let Some(ref mut fields) = tab.fields {
+                    fields.sort_by_key(|field| field.sequence.unwrap_or(0));
                 }
             }
-        })
+        }
     }
 }
 
@@ -277,139 +439,3 @@ pub struct Table {
     pub identifier_columns: Option<Vec<String>>,
     pub selection_colums: Option<Vec<String>>,
 }
-
-pub async fn window_from_id(_id: Option<i32>, _language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<Window, String> {
-	if _id.is_none() || _id.map(|id| id <= 0).unwrap_or(false) {
-		return Err(Error::new(ErrorKind::InvalidData.into(), "Window Identifier is Mandatory").to_string());
-	}
-    let mut _document = Window::from_id(_id);
-
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error to {:?}: {:?}", _id.to_owned(), error.to_string());
-			return Err(error.to_string())
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-    _document.index_value = Some(_index_name);
-    let _window_document: &dyn IndexDocument = &_document;
-    match get_by_id(_window_document).await {
-        Ok(value) => {
-			let mut window: Window = serde_json::from_value(value).unwrap();
-            log::info!("Finded Value: {:?}", window.id);
-
-			// sort tabs by sequence
-			if let Some(ref mut tabs) = window.tabs {
-				tabs.sort_by_key(|tab| tab.sequence.clone().unwrap_or(0));
-				for tab in tabs.iter_mut() {
-					// sort fields by sequence
-					if let Some(ref mut fields) = tab.fields {
-						fields.sort_by_key(|field| field.sequence.clone().unwrap_or(0));
-					}
-				}
-			}
-
-            Ok(window)
-        },
-        Err(error) => {
-			log::error!("{}", error);
-            Err(error)
-        },
-    }
-}
-
-async fn get_index_name(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>) -> Result<String, std::io::Error> {
-    //  Validate
-    if _language.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Language is Mandatory"));
-    }
-    if _client_id.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Client is Mandatory"));
-    }
-    if _role_id.is_none() {
-        return Err(Error::new(ErrorKind::InvalidData.into(), "Role is Mandatory"));
-    }
-
-	let _index: String = "window".to_string();
-
-	let _user_index = user_index(_index.to_owned(), _language, _client_id, _role_id, _user_id);
-    let _role_index = role_index(_index.to_owned(), _language, _client_id, _role_id);
-	let _client_index = client_index(_index.to_owned(), _language, _client_id);
-
-    //  Find index
-    match exists_index(_user_index.to_owned()).await {
-		Ok(_) => {
-			log::info!("Find with user index `{:}`", _user_index);
-			Ok(_user_index)
-		},
-        Err(_) => {
-			log::warn!("No user index `{:}`", _user_index);
-            match exists_index(_role_index.to_owned()).await {
-                Ok(_) => {
-					log::info!("Find with role index `{:}`", _role_index);
-					Ok(_role_index)
-				},
-				Err(error) => {
-					log::warn!("No role index `{:}`", _role_index);
-					match exists_index(_client_index.to_owned()).await {
-						Ok(_) => {
-							log::info!("Find with client index `{:}`", _client_index);
-							Ok(_client_index)
-						},
-						Err(_) => {
-							log::error!("No client index `{:}`", _client_index);
-							return Err(Error::new(ErrorKind::InvalidData.into(), error))
-						}
-					}
-                }
-            }
-        }
-    }
-}
-
-pub async fn windows(_language: Option<&String>, _client_id: Option<&String>, _role_id: Option<&String>, _user_id: Option<&String>, _search_value: Option<&String>) -> Result<WindowListResponse, std::io::Error> {
-    let _search_value = match _search_value {
-        Some(value) => value.clone(),
-        None => "".to_owned()
-    };
-
-	//  Find index
-	let _index_name = match get_index_name(_language, _client_id, _role_id, _user_id).await {
-		Ok(index_name) => index_name,
-		Err(error) => {
-			log::error!("Index name error: {:?}", error.to_string());
-			return Err(Error::new(ErrorKind::InvalidData.into(), error))
-		}
-	};
-	log::info!("Index to search {:}", _index_name);
-
-    let mut _document = Window::default();
-    _document.index_value = Some(_index_name);
-    let _window_document: &dyn IndexDocument = &_document;
-    match find(_window_document, _search_value, 0, 10).await {
-        Ok(values) => {
-            let mut windows_list: Vec<Window> = vec![];
-            for value in values {
-				let mut window: Window = serde_json::from_value(value).unwrap();
-				// sort tabs by sequence
-				if let Some(ref mut tabs) = window.tabs {
-					tabs.sort_by_key(|tab| tab.sequence.clone().unwrap_or(0));
-					for tab in tabs.iter_mut() {
-						// sort fields by sequence
-						if let Some(ref mut fields) = tab.fields {
-							fields.sort_by_key(|field| field.sequence.clone().unwrap_or(0));
-						}
-					}
-				}
-                windows_list.push(window.to_owned());
-            }
-
-            Ok(WindowListResponse {
-                windows: Some(windows_list)
-            })
-        },
-        Err(error) => Err(Error::new(ErrorKind::InvalidData.into(), error))
-    }
-}